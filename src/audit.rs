@@ -0,0 +1,23 @@
+use {
+    chrono::{DateTime, Utc},
+    serde::Serialize,
+};
+
+/// Status recorded when signing was skipped because the key's slot was already signed.
+pub const SIGNATURE_ALREADY_PRESENT: &str = "SIGNATURE_ALREADY_PRESENT";
+
+/// A compact record of a single signing operation, suitable for appending to an audit log.
+///
+/// Records contain identifiers only and never include key material or the signed payload.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub organization_id: String,
+    pub private_key_id: String,
+    /// The Turnkey activity that produced the signature, or `None` if signing was skipped.
+    pub activity_id: Option<String>,
+    pub status: String,
+    pub signature_base58: String,
+    pub message_hex_len: usize,
+}
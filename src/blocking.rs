@@ -0,0 +1,591 @@
+//! A synchronous wrapper around the async `Turnkey` client.
+//!
+//! `turnkey::blocking::Turnkey` owns an internal current-thread Tokio runtime and blocks on
+//! the async client's futures, so it can be used from code that has no runtime of its own.
+//! Its methods mirror those of `turnkey::Turnkey`.
+//!
+//! The blocking client must not be used from within an async runtime; doing so panics.
+//!
+//! ```no_run
+//! use {
+//!     solana_sdk::{message::Message, pubkey::Pubkey, system_instruction, transaction::Transaction},
+//!     turnkey::{blocking::Turnkey, KeySelector},
+//! };
+//!
+//! let turnkey_client = Turnkey::new().unwrap();
+//! let payer = Pubkey::new_unique();
+//! let instruction = system_instruction::transfer(&payer, &payer, 100);
+//! let mut transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+//!
+//! let signature = turnkey_client
+//!     .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+//!     .unwrap();
+//! ```
+
+use {
+    crate::{
+        audit::AuditRecord,
+        client::{
+            Curve, KeySelector, PublicStatus, SignatureComponents, SignedMessage,
+            SignedTransactionBytes,
+        },
+        config::SecretBundle,
+        errors::{TurnkeyError, TurnkeyResult},
+        logging::StampLogging,
+        models::{
+            Activity, ActivityResponse, ApiVersion, CreateSubOrgParams, CreateSubOrgResult,
+            CreateWalletResult, ExportResult, ImportResult, WalletAccount, WalletAccountParams,
+            WhoAmI,
+        },
+        polling::EscalationDecision,
+        retry::RetryPolicy,
+        stamp::Stamper,
+        transaction::{SignaturePolicy, TransactionEncoding},
+        transport::TurnkeyTransport,
+    },
+    base64::engine::GeneralPurpose,
+    solana_sdk::{
+        commitment_config::CommitmentConfig, message::Message, pubkey::Pubkey,
+        signature::Signature, transaction::Transaction,
+    },
+    std::{future::Future, path::Path, time::Duration},
+    tokio::runtime::{Builder, Runtime},
+};
+
+/// A synchronous Turnkey client.
+///
+/// Every method blocks the calling thread until the underlying request completes. See
+/// `turnkey::Turnkey` for details on each method.
+pub struct Turnkey {
+    inner: crate::Turnkey,
+    runtime: Runtime,
+}
+
+impl Turnkey {
+    /// Creates a new blocking client from environment variables.
+    ///
+    /// See `turnkey::Turnkey::new`.
+    pub fn new() -> TurnkeyResult<Self> {
+        Self::from_async(crate::Turnkey::new()?)
+    }
+
+    /// Creates a new blocking client from environment variables.
+    ///
+    /// See `turnkey::Turnkey::from_env`.
+    pub fn from_env() -> TurnkeyResult<Self> {
+        Self::from_async(crate::Turnkey::from_env()?)
+    }
+
+    /// Creates a new blocking client from environment variables without loading `.env` files.
+    ///
+    /// See `turnkey::Turnkey::new_without_dotenv`.
+    pub fn new_without_dotenv() -> TurnkeyResult<Self> {
+        Self::from_async(crate::Turnkey::new_without_dotenv()?)
+    }
+
+    /// Creates a new blocking client from a key file.
+    ///
+    /// See `turnkey::Turnkey::from_key_file`.
+    pub fn from_key_file(path: impl AsRef<Path>) -> TurnkeyResult<Self> {
+        Self::from_async(crate::Turnkey::from_key_file(path)?)
+    }
+
+    /// Creates a new blocking client from a secret bundle.
+    ///
+    /// See `turnkey::Turnkey::from_secret_bundle`.
+    pub fn from_secret_bundle(bundle: SecretBundle) -> TurnkeyResult<Self> {
+        Self::from_async(crate::Turnkey::from_secret_bundle(bundle)?)
+    }
+
+    /// Wraps an already configured async client.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if the internal runtime cannot be created.
+    pub fn from_async(inner: crate::Turnkey) -> TurnkeyResult<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                TurnkeyError::OtherError(format!("Failed to create blocking runtime: {}", e))
+            })?;
+
+        Ok(Self { inner, runtime })
+    }
+
+    /// Returns the wrapped async client.
+    pub fn into_async(self) -> crate::Turnkey {
+        self.inner
+    }
+
+    fn map_inner(mut self, f: impl FnOnce(crate::Turnkey) -> crate::Turnkey) -> Self {
+        self.inner = f(self.inner);
+        self
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// See `turnkey::Turnkey::with_commitment`.
+    pub fn with_commitment(self, commitment: CommitmentConfig) -> Self {
+        self.map_inner(|inner| inner.with_commitment(commitment))
+    }
+
+    /// See `turnkey::Turnkey::with_confirmation_timeout`.
+    pub fn with_confirmation_timeout(self, confirmation_timeout: Duration) -> Self {
+        self.map_inner(|inner| inner.with_confirmation_timeout(confirmation_timeout))
+    }
+
+    /// See `turnkey::Turnkey::with_backup_api_key`.
+    pub fn with_backup_api_key(
+        mut self,
+        public_key: impl Into<String>,
+        private_key: impl Into<String>,
+    ) -> TurnkeyResult<Self> {
+        self.inner = self.inner.with_backup_api_key(public_key, private_key)?;
+        Ok(self)
+    }
+
+    /// See `turnkey::Turnkey::with_http_client`.
+    pub fn with_http_client(self, client: reqwest::Client) -> Self {
+        self.map_inner(|inner| inner.with_http_client(client))
+    }
+
+    /// See `turnkey::Turnkey::with_transport`.
+    pub fn with_transport(self, transport: impl TurnkeyTransport + 'static) -> Self {
+        self.map_inner(|inner| inner.with_transport(transport))
+    }
+
+    /// See `turnkey::Turnkey::with_max_concurrent_requests`.
+    pub fn with_max_concurrent_requests(self, max_concurrent_requests: usize) -> Self {
+        self.map_inner(|inner| inner.with_max_concurrent_requests(max_concurrent_requests))
+    }
+
+    /// See `turnkey::Turnkey::with_base64_engine`.
+    pub fn with_base64_engine(self, engine: GeneralPurpose) -> Self {
+        self.map_inner(|inner| inner.with_base64_engine(engine))
+    }
+
+    /// See `turnkey::Turnkey::with_signature_scheme`.
+    pub fn with_signature_scheme(self, scheme: impl Into<String>) -> Self {
+        self.map_inner(|inner| inner.with_signature_scheme(scheme))
+    }
+
+    /// See `turnkey::Turnkey::with_stamper`.
+    pub fn with_stamper(self, stamper: impl Stamper + 'static) -> Self {
+        self.map_inner(|inner| inner.with_stamper(stamper))
+    }
+
+    /// See `turnkey::Turnkey::with_api_version`.
+    pub fn with_api_version(self, api_version: ApiVersion) -> Self {
+        self.map_inner(|inner| inner.with_api_version(api_version))
+    }
+
+    /// See `turnkey::Turnkey::with_activity_polling`.
+    pub fn with_activity_polling(self, interval: Duration, timeout: Duration) -> Self {
+        self.map_inner(|inner| inner.with_activity_polling(interval, timeout))
+    }
+
+    /// See `turnkey::Turnkey::with_escalation_policy`.
+    pub fn with_escalation_policy(
+        self,
+        policy: impl Fn(&Activity, u32) -> EscalationDecision + Send + Sync + 'static,
+    ) -> Self {
+        self.map_inner(|inner| inner.with_escalation_policy(policy))
+    }
+
+    /// See `turnkey::Turnkey::with_signature_policy`.
+    pub fn with_signature_policy(self, policy: SignaturePolicy) -> Self {
+        self.map_inner(|inner| inner.with_signature_policy(policy))
+    }
+
+    /// See `turnkey::Turnkey::with_activity_type_verification`.
+    pub fn with_activity_type_verification(self, enabled: bool) -> Self {
+        self.map_inner(|inner| inner.with_activity_type_verification(enabled))
+    }
+
+    /// See `turnkey::Turnkey::with_retry_policy`.
+    pub fn with_retry_policy(self, policy: RetryPolicy) -> Self {
+        self.map_inner(|inner| inner.with_retry_policy(policy))
+    }
+
+    /// See `turnkey::Turnkey::with_max_stamp_age`.
+    pub fn with_max_stamp_age(self, max_stamp_age: Duration) -> Self {
+        self.map_inner(|inner| inner.with_max_stamp_age(max_stamp_age))
+    }
+
+    /// See `turnkey::Turnkey::with_stamp_logging`.
+    pub fn with_stamp_logging(self, stamp_logging: StampLogging) -> Self {
+        self.map_inner(|inner| inner.with_stamp_logging(stamp_logging))
+    }
+
+    /// See `turnkey::Turnkey::with_batch_concurrency`.
+    pub fn with_batch_concurrency(self, batch_concurrency: usize) -> Self {
+        self.map_inner(|inner| inner.with_batch_concurrency(batch_concurrency))
+    }
+
+    /// See `turnkey::Turnkey::with_max_payload_size`.
+    pub fn with_max_payload_size(self, max_payload_size: usize) -> Self {
+        self.map_inner(|inner| inner.with_max_payload_size(max_payload_size))
+    }
+
+    /// See `turnkey::Turnkey::with_max_response_size`.
+    pub fn with_max_response_size(self, max_response_size: usize) -> Self {
+        self.map_inner(|inner| inner.with_max_response_size(max_response_size))
+    }
+
+    /// See `turnkey::Turnkey::with_user_agent`.
+    pub fn with_user_agent(mut self, user_agent: &str) -> TurnkeyResult<Self> {
+        self.inner = self.inner.with_user_agent(user_agent)?;
+        Ok(self)
+    }
+
+    /// See `turnkey::Turnkey::with_header`.
+    pub fn with_header(mut self, name: &str, value: &str) -> TurnkeyResult<Self> {
+        self.inner = self.inner.with_header(name, value)?;
+        Ok(self)
+    }
+
+    /// See `turnkey::Turnkey::commitment`.
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.inner.commitment()
+    }
+
+    /// See `turnkey::Turnkey::confirmation_timeout`.
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.inner.confirmation_timeout()
+    }
+
+    /// See `turnkey::Turnkey::organization_id`.
+    pub fn organization_id(&self) -> &str {
+        self.inner.organization_id()
+    }
+
+    /// See `turnkey::Turnkey::api_public_key`.
+    pub fn api_public_key(&self) -> &str {
+        self.inner.api_public_key()
+    }
+
+    /// See `turnkey::Turnkey::public_key`.
+    pub fn public_key(&self, selector: KeySelector) -> TurnkeyResult<Pubkey> {
+        self.inner.public_key(selector)
+    }
+
+    /// See `turnkey::Turnkey::public_status`.
+    pub fn public_status(&self) -> PublicStatus {
+        self.inner.public_status()
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction`.
+    pub fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_transaction(transaction, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_at`.
+    pub fn sign_transaction_at(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        timestamp_ms: i64,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(
+            self.inner
+                .sign_transaction_at(transaction, key_selector, timestamp_ms),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_with_retry_policy`.
+    pub fn sign_transaction_with_retry_policy(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_transaction_with_retry_policy(
+            transaction,
+            key_selector,
+            retry_policy,
+        ))
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_with_audit`.
+    pub fn sign_transaction_with_audit(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<(Signature, AuditRecord)> {
+        self.block_on(
+            self.inner
+                .sign_transaction_with_audit(transaction, key_selector),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_as_fee_payer_auto`.
+    pub fn sign_as_fee_payer_auto(
+        &self,
+        transaction: &mut Transaction,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_as_fee_payer_auto(transaction))
+    }
+
+    /// See `turnkey::Turnkey::sign_offchain_message`.
+    pub fn sign_offchain_message(
+        &self,
+        message: &[u8],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_offchain_message(message, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_detached`.
+    pub fn sign_transaction_detached(
+        &self,
+        transaction: &Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(
+            self.inner
+                .sign_transaction_detached(transaction, key_selector),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_message_struct`.
+    pub fn sign_message_struct(
+        &self,
+        message: &Message,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_message_struct(message, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_transactions`.
+    pub fn sign_transactions(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.block_on(self.inner.sign_transactions(transactions, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_transactions_with_retry_policy`.
+    pub fn sign_transactions_with_retry_policy(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.block_on(self.inner.sign_transactions_with_retry_policy(
+            transactions,
+            key_selector,
+            retry_policy,
+        ))
+    }
+
+    /// See `turnkey::Turnkey::sign_transactions_collect`.
+    pub fn sign_transactions_collect(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+    ) -> Vec<TurnkeyResult<Signature>> {
+        self.block_on(
+            self.inner
+                .sign_transactions_collect(transactions, key_selector),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_transactions_collect_with_retry_policy`.
+    pub fn sign_transactions_collect_with_retry_policy(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> Vec<TurnkeyResult<Signature>> {
+        self.block_on(self.inner.sign_transactions_collect_with_retry_policy(
+            transactions,
+            key_selector,
+            retry_policy,
+        ))
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_multi`.
+    pub fn sign_transaction_multi(
+        &self,
+        transaction: &mut Transaction,
+        key_selectors: &[KeySelector],
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.block_on(
+            self.inner
+                .sign_transaction_multi(transaction, key_selectors),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_multi_with_retry_policy`.
+    pub fn sign_transaction_multi_with_retry_policy(
+        &self,
+        transaction: &mut Transaction,
+        key_selectors: &[KeySelector],
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.block_on(self.inner.sign_transaction_multi_with_retry_policy(
+            transaction,
+            key_selectors,
+            retry_policy,
+        ))
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_with_message`.
+    pub fn sign_transaction_with_message(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignedMessage> {
+        self.block_on(
+            self.inner
+                .sign_transaction_with_message(transaction, key_selector),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_with_bytes`.
+    pub fn sign_transaction_with_bytes(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignedTransactionBytes> {
+        self.block_on(
+            self.inner
+                .sign_transaction_with_bytes(transaction, key_selector),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_transaction_encoded`.
+    pub fn sign_transaction_encoded(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        encoding: TransactionEncoding,
+    ) -> TurnkeyResult<String> {
+        self.block_on(
+            self.inner
+                .sign_transaction_encoded(transaction, key_selector, encoding),
+        )
+    }
+
+    /// See `turnkey::Turnkey::sign_raw_components`.
+    pub fn sign_raw_components(
+        &self,
+        bytes: &[u8],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignatureComponents> {
+        self.block_on(self.inner.sign_raw_components(bytes, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_raw_components_with_retry_policy`.
+    pub fn sign_raw_components_with_retry_policy(
+        &self,
+        bytes: &[u8],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<SignatureComponents> {
+        self.block_on(self.inner.sign_raw_components_with_retry_policy(
+            bytes,
+            key_selector,
+            retry_policy,
+        ))
+    }
+
+    /// See `turnkey::Turnkey::sign_digest`.
+    pub fn sign_digest(
+        &self,
+        digest: &[u8; 32],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(self.inner.sign_digest(digest, key_selector))
+    }
+
+    /// See `turnkey::Turnkey::sign_digest_with_retry_policy`.
+    pub fn sign_digest_with_retry_policy(
+        &self,
+        digest: &[u8; 32],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Signature> {
+        self.block_on(
+            self.inner
+                .sign_digest_with_retry_policy(digest, key_selector, retry_policy),
+        )
+    }
+
+    /// See `turnkey::Turnkey::create_wallet`.
+    pub fn create_wallet(
+        &self,
+        name: &str,
+        accounts: &[WalletAccountParams],
+    ) -> TurnkeyResult<CreateWalletResult> {
+        self.block_on(self.inner.create_wallet(name, accounts))
+    }
+
+    /// See `turnkey::Turnkey::create_sub_organization`.
+    pub fn create_sub_organization(
+        &self,
+        params: CreateSubOrgParams,
+    ) -> TurnkeyResult<CreateSubOrgResult> {
+        self.block_on(self.inner.create_sub_organization(params))
+    }
+
+    /// See `turnkey::Turnkey::export_private_key`.
+    pub fn export_private_key(
+        &self,
+        private_key_id: &str,
+        target_public_key: &str,
+    ) -> TurnkeyResult<ExportResult> {
+        self.block_on(
+            self.inner
+                .export_private_key(private_key_id, target_public_key),
+        )
+    }
+
+    /// See `turnkey::Turnkey::init_import_private_key`.
+    pub fn init_import_private_key(&self) -> TurnkeyResult<String> {
+        self.block_on(self.inner.init_import_private_key())
+    }
+
+    /// See `turnkey::Turnkey::import_private_key`.
+    pub fn import_private_key(
+        &self,
+        encrypted_bundle: &str,
+        name: &str,
+        curve: Curve,
+    ) -> TurnkeyResult<ImportResult> {
+        self.block_on(self.inner.import_private_key(encrypted_bundle, name, curve))
+    }
+
+    /// See `turnkey::Turnkey::get_activity`.
+    pub fn get_activity(&self, activity_id: &str) -> TurnkeyResult<ActivityResponse> {
+        self.block_on(self.inner.get_activity(activity_id))
+    }
+
+    /// See `turnkey::Turnkey::whoami`.
+    pub fn whoami(&self) -> TurnkeyResult<WhoAmI> {
+        self.block_on(self.inner.whoami())
+    }
+
+    /// See `turnkey::Turnkey::check_ready`.
+    pub fn check_ready(&self) -> TurnkeyResult<()> {
+        self.block_on(self.inner.check_ready())
+    }
+
+    /// See `turnkey::Turnkey::get_wallet_accounts`.
+    pub fn get_wallet_accounts(&self, wallet_id: &str) -> TurnkeyResult<Vec<WalletAccount>> {
+        self.block_on(self.inner.get_wallet_accounts(wallet_id))
+    }
+
+    /// See `turnkey::Turnkey::wait_for_activity`.
+    pub fn wait_for_activity(&self, activity_id: &str) -> TurnkeyResult<ActivityResponse> {
+        self.block_on(self.inner.wait_for_activity(activity_id))
+    }
+}
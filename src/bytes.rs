@@ -1,4 +1,18 @@
+/// Lowercase hexadecimal digits, indexed by nibble value.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Decodes a hexadecimal string into bytes.
+///
+/// Returns an error rather than panicking if `hex` has an odd length or contains
+/// anything other than ASCII hex digits.
 pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err("hex string contains non-hex characters".into());
+    }
+    if !hex.len().is_multiple_of(2) {
+        return Err(format!("hex string has odd length {}", hex.len()).into());
+    }
+
     (0..hex.len())
         .step_by(2)
         .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
@@ -6,6 +20,45 @@ pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         .map_err(|e| e.into())
 }
 
+/// Encodes bytes as a lowercase hexadecimal string.
+///
+/// The output is written into a single pre-allocated `String`, so encoding large payloads
+/// does not allocate per byte.
 pub fn bytes_to_hex(bytes: &[u8]) -> Result<String, Box<dyn std::error::Error>> {
-    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        hex.push(HEX_DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    Ok(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes_to_hex, hex_to_bytes};
+
+    #[test]
+    fn test_bytes_to_hex_matches_format_on_large_buffer() {
+        let bytes = (0..10 * 1024).map(|i| (i % 256) as u8).collect::<Vec<_>>();
+
+        let expected = bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        let hex = bytes_to_hex(&bytes).expect("Encoding should not fail");
+
+        assert_eq!(hex, expected);
+        assert_eq!(hex_to_bytes(&hex).expect("Decoding should not fail"), bytes);
+    }
+
+    #[test]
+    fn test_hex_to_bytes_rejects_malformed_input() {
+        assert!(hex_to_bytes("abc").is_err());
+        assert!(hex_to_bytes("zz").is_err());
+        assert!(hex_to_bytes("é0").is_err());
+        assert_eq!(
+            hex_to_bytes("").expect("Decoding should not fail"),
+            Vec::<u8>::new()
+        );
+    }
 }
@@ -1,238 +1,2655 @@
 use {
     crate::{
-        bytes::{bytes_to_hex, hex_to_bytes},
+        audit::{AuditRecord, SIGNATURE_ALREADY_PRESENT},
+        bytes::bytes_to_hex,
+        config::{KeyFile, KeyFileKey, SecretBundle},
         errors::{TurnkeyError, TurnkeyResponseError, TurnkeyResult},
+        logging::StampLogging,
         models::{
-            ActivityResponse, ApiStamp, SignRawPayloadIntentV2Parameters, SignRawPayloadRequest,
+            Activity, ActivityResponse, ApiVersion, CreateSubOrgParams, CreateSubOrgRequest,
+            CreateSubOrgResult, CreateWalletIntentParameters, CreateWalletRequest,
+            CreateWalletResult, ExportPrivateKeyIntentParameters, ExportPrivateKeyRequest,
+            ExportResult, GetActivityRequest, GetWalletAccountsRequest, GetWalletAccountsResponse,
+            ImportPrivateKeyIntentParameters, ImportPrivateKeyRequest, ImportResult,
+            InitImportPrivateKeyIntentParameters, InitImportPrivateKeyRequest,
+            RejectActivityIntentParameters, RejectActivityRequest,
+            SignRawPayloadIntentV2Parameters, SignRawPayloadRequest, WalletAccount,
+            WalletAccountParams, WhoAmI, WhoAmIRequest,
         },
+        polling::{
+            ActivityPolling, EscalationDecision, EscalationPolicy, PENDING_ACTIVITY_STATUSES,
+        },
+        retry::{is_retryable, RetryPolicy},
+        signature::assemble_signature,
+        stamp::{ApiKeyStamper, Stamp, Stamper},
+        transaction::{
+            encode_transaction, ensure_signature_slots, fee_payer, required_signer_index,
+            serialize_transaction, SignaturePolicy, TransactionEncoding,
+        },
+        transport::{ReqwestTransport, TransportResponse, TurnkeyTransport},
     },
-    base64_url,
+    base64::engine::GeneralPurpose,
     dotenv::dotenv,
-    p256::ecdsa::{signature::Signer, SigningKey},
-    reqwest::Client,
-    serde::Deserialize,
-    solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction},
-    std::{env, str::FromStr},
+    futures::{
+        future::try_join_all,
+        stream::{self, StreamExt, TryStreamExt},
+    },
+    reqwest::{
+        header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, USER_AGENT},
+        StatusCode,
+    },
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        commitment_config::CommitmentConfig, hash::Hash, message::Message,
+        offchain_message::OffchainMessage, pubkey::Pubkey, signature::Signature,
+        transaction::Transaction,
+    },
+    std::{env, fs, path::Path, str::FromStr, sync::Arc, time::Duration},
+    tokio::{
+        sync::{Semaphore, SemaphorePermit},
+        time::Instant,
+    },
 };
 
-/// Represents the Turnkey service client, encapsulating all necessary keys and the API client.
+/// Base URL of the Turnkey public API.
+const TURNKEY_API_URL: &str = "https://api.turnkey.com";
+
+/// Default maximum size of a payload sent for signing, in bytes.
+///
+/// This comfortably fits every Solana payload: transactions are at most 1232 bytes and
+/// serialized off-chain messages at most 65535 bytes.
+const DEFAULT_MAX_PAYLOAD_SIZE: usize = 64 * 1024;
+
+/// Default time the RPC helpers wait for a sent transaction to be confirmed.
+const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How many characters of a rejected response body are included in its error.
+const RESPONSE_SNIPPET_LEN: usize = 256;
+
+/// Response header holding the ID Turnkey assigns to each request.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Default `User-Agent` sent with every request.
+const DEFAULT_USER_AGENT: &str = concat!("turnkey-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Represents the Turnkey service client, encapsulating all necessary keys and the API transport.
 pub struct Turnkey {
-    api_public_key: String,
-    api_private_key: String,
+    api_keys: Vec<ApiKeyStamper>,
     organization_id: String,
-    example_key_info: KeyInfo,
-    client: Client,
+    example_key_info: Option<KeyInfo>,
+    transport: Arc<dyn TurnkeyTransport>,
+    request_limiter: Option<Arc<Semaphore>>,
+    stamper: Option<Arc<dyn Stamper>>,
+    api_version: ApiVersion,
+    activity_polling: Option<ActivityPolling>,
+    escalation_policy: EscalationPolicy,
+    signature_policy: SignaturePolicy,
+    verify_activity_type: bool,
+    retry_policy: RetryPolicy,
+    max_stamp_age: Duration,
+    stamp_logging: StampLogging,
+    batch_concurrency: usize,
+    max_payload_size: usize,
+    max_response_size: Option<usize>,
+    headers: HeaderMap,
+    commitment: CommitmentConfig,
+    confirmation_timeout: Duration,
+}
+
+/// Holds the private key ID and corresponding public key for a specific operation.
+#[derive(Clone)]
+pub struct KeyInfo {
+    private_key_id: String,
+    public_key: Pubkey,
+    curve: Curve,
+}
+
+/// Per-call overrides for a signing request.
+#[derive(Clone, Copy, Default)]
+struct SignOptions<'a> {
+    /// The activity timestamp, or `None` to stamp the request with the current time.
+    timestamp_ms: Option<i64>,
+    /// The retry policy, or `None` to use the client's default policy.
+    retry_policy: Option<&'a RetryPolicy>,
+}
+
+impl<'a> SignOptions<'a> {
+    /// Returns options that only override the retry policy.
+    fn with_retry_policy(retry_policy: &'a RetryPolicy) -> Self {
+        SignOptions {
+            retry_policy: Some(retry_policy),
+            ..SignOptions::default()
+        }
+    }
+}
+
+/// The curve of a Turnkey-managed signing key, which determines how signatures are assembled
+/// and verified.
+///
+/// This only describes the signing key; API requests are always stamped with P256.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Curve {
+    /// A P256 key, whose signature is the concatenation of the returned `r` and `s` values.
+    #[default]
+    P256,
+    /// An ed25519 key, which signs Solana payloads natively and produces a 64-byte signature
+    /// that is verified against the key's public key.
+    Ed25519,
+}
+
+impl Curve {
+    /// Returns the name of the curve in the Turnkey API, e.g. `CURVE_ED25519`.
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            Curve::P256 => "CURVE_P256",
+            Curve::Ed25519 => "CURVE_ED25519",
+        }
+    }
+
+    /// Returns the address formats Turnkey derives for an imported key of this curve.
+    fn address_formats(&self) -> Vec<String> {
+        let address_format = match self {
+            Curve::P256 => "ADDRESS_FORMAT_COMPRESSED",
+            Curve::Ed25519 => "ADDRESS_FORMAT_SOLANA",
+        };
+        vec![address_format.to_string()]
+    }
+}
+
+impl FromStr for Curve {
+    type Err = TurnkeyError;
+
+    fn from_str(curve: &str) -> TurnkeyResult<Self> {
+        match curve.to_ascii_lowercase().as_str() {
+            "p256" | "curve_p256" => Ok(Curve::P256),
+            "ed25519" | "curve_ed25519" => Ok(Curve::Ed25519),
+            _ => Err(TurnkeyError::OtherError(format!(
+                "Unknown key curve {}",
+                curve
+            ))),
+        }
+    }
+}
+
+impl KeyInfo {
+    /// Creates key information for a Turnkey private key and its public key.
+    ///
+    /// The key's curve defaults to P256; use `with_curve` for ed25519 keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `private_key_id` - The Turnkey identifier of the private key.
+    /// * `public_key` - The Solana public key corresponding to the private key.
+    ///
+    pub fn new(private_key_id: impl Into<String>, public_key: Pubkey) -> Self {
+        Self {
+            private_key_id: private_key_id.into(),
+            public_key,
+            curve: Curve::default(),
+        }
+    }
+
+    /// Creates key information from a base58-encoded public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `private_key_id` - The Turnkey identifier of the private key.
+    /// * `public_key` - The base58-encoded Solana public key corresponding to the private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if `public_key` is not a valid base58 public key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use turnkey::client::KeyInfo;
+    ///
+    /// assert!(KeyInfo::from_base58("private-key-id", "11111111111111111111111111111111").is_ok());
+    /// assert!(KeyInfo::from_base58("private-key-id", "not-a-pubkey").is_err());
+    /// ```
+    pub fn from_base58(private_key_id: impl Into<String>, public_key: &str) -> TurnkeyResult<Self> {
+        let public_key = Pubkey::from_str(public_key).map_err(|e| {
+            TurnkeyError::OtherError(format!("Invalid public key {}: {}", public_key, e))
+        })?;
+
+        Ok(Self::new(private_key_id, public_key))
+    }
+
+    /// Sets the curve of the key, which determines how its signatures are verified.
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Returns the Turnkey identifier of the private key.
+    pub fn private_key_id(&self) -> &str {
+        &self.private_key_id
+    }
+
+    /// Returns the Solana public key corresponding to the private key.
+    pub fn public_key(&self) -> Pubkey {
+        self.public_key
+    }
+
+    /// Returns the curve of the key.
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Builds key information from a key entry of a `KeyFile` or `SecretBundle`.
+    fn from_key_file_key(key: KeyFileKey) -> TurnkeyResult<Self> {
+        let curve = key
+            .curve
+            .as_deref()
+            .map(Curve::from_str)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self::from_base58(key.private_key_id, &key.public_key)?.with_curve(curve))
+    }
+}
+
+/// Enumerates the selectable keys for operations, distinguishing by their use case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySelector {
+    ExampleKey,
+    // other key info variants depending on what other keys you need to sign with
 }
 
-/// Holds the private key ID and corresponding public key for a specific operation.
-#[derive(Clone)]
-pub struct KeyInfo {
-    private_key_id: String,
-    public_key: Pubkey,
-}
+impl KeySelector {
+    /// Returns a human-readable label identifying the key, suitable for logs and status reports.
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeySelector::ExampleKey => "example",
+            // add a label for each new variant
+        }
+    }
+}
+
+/// A snapshot of the client's non-secret configuration.
+///
+/// Intended for health and status endpoints; it never includes API private keys or
+/// stamps.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicStatus {
+    pub organization_id: String,
+    pub base_url: String,
+    pub key_labels: Vec<String>,
+    pub key_count: usize,
+    pub api_key_count: usize,
+}
+
+/// The signature of a transaction together with the message data it was produced over.
+///
+/// `message_hash` is the blake3 hash the Solana runtime derives from `message_data`, which
+/// gives a stable identifier for the signed message that is independent of the signature.
+pub struct SignedMessage {
+    pub signature: Signature,
+    pub message_data: Vec<u8>,
+    pub message_hash: Hash,
+}
+
+/// The components of a raw payload signature as returned by Turnkey.
+///
+/// `r`, `s` and `v` are the hex strings from the `SIGN_RAW_PAYLOAD` result; `v` is only
+/// present for curves with a recovery id. `signature_bytes` is the assembled `r || s`
+/// signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureComponents {
+    pub r: String,
+    pub s: String,
+    pub v: Option<String>,
+    pub signature_bytes: Vec<u8>,
+}
+
+/// The serialized bytes of a transaction before and after signing, together with the signature.
+///
+/// Diffing `unsigned_bytes` against `signed_bytes` shows exactly which signature slot was
+/// filled in.
+pub struct SignedTransactionBytes {
+    pub unsigned_bytes: Vec<u8>,
+    pub signed_bytes: Vec<u8>,
+    pub signature: Signature,
+}
+
+impl Turnkey {
+    /// Creates a new instance of the Turnkey client.
+    ///
+    /// This is equivalent to `Turnkey::from_env`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use turnkey::client::Turnkey;
+    ///
+    /// let turnkey_client = Turnkey::new();
+    /// ```
+    pub fn new() -> TurnkeyResult<Self> {
+        Self::from_env()
+    }
+
+    /// Creates a new instance of the Turnkey client from environment variables.
+    ///
+    /// Variables are read from the process environment, after loading a `.env` file from
+    /// the working directory if one exists. The following variables are required:
+    /// `TURNKEY_API_PUBLIC_KEY`, `TURNKEY_API_PRIVATE_KEY`, `TURNKEY_ORGANIZATION_ID`,
+    /// `TURNKEY_EXAMPLE_PRIVATE_KEY_ID` and `TURNKEY_EXAMPLE_PUBLIC_KEY`. The curve of the
+    /// example key may be set with `TURNKEY_EXAMPLE_KEY_CURVE` and defaults to P256.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::MissingConfig` naming the first required variable that is not
+    /// set.
+    pub fn from_env() -> TurnkeyResult<Self> {
+        dotenv().ok();
+
+        Self::new_without_dotenv()
+    }
+
+    /// Creates a new instance of the Turnkey client from the process environment only.
+    ///
+    /// Behaves exactly like `Turnkey::from_env`, but never loads a `.env` file, so only
+    /// variables set in the process environment are used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::MissingConfig` naming the first required variable that is not
+    /// set.
+    pub fn new_without_dotenv() -> TurnkeyResult<Self> {
+        let api_key = ApiKeyStamper::new(
+            required_env_var("TURNKEY_API_PUBLIC_KEY")?,
+            required_env_var("TURNKEY_API_PRIVATE_KEY")?,
+        )?;
+        let example_key_info = KeyInfo {
+            private_key_id: required_env_var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID")?,
+            public_key: Pubkey::from_str(&required_env_var("TURNKEY_EXAMPLE_PUBLIC_KEY")?)?,
+            curve: env::var("TURNKEY_EXAMPLE_KEY_CURVE")
+                .ok()
+                .map(|curve| Curve::from_str(&curve))
+                .transpose()?
+                .unwrap_or_default(),
+        };
+
+        Ok(Self::from_parts(
+            required_env_var("TURNKEY_ORGANIZATION_ID")?,
+            api_key,
+            Some(example_key_info),
+        ))
+    }
+
+    /// Creates a new instance of the Turnkey client from a key file.
+    ///
+    /// The file holds the organization ID and API key pair, and optionally the example
+    /// signing key; see `KeyFile` for the format. Files with a `.toml` extension are parsed
+    /// as TOML, all others as JSON. If the file has no example key, signing with
+    /// `KeySelector::ExampleKey` returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the key file to load.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if the file cannot be read, is malformed, or is
+    /// missing a required field.
+    pub fn from_key_file(path: impl AsRef<Path>) -> TurnkeyResult<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| {
+            TurnkeyError::OtherError(format!("Failed to read key file {}: {}", path.display(), e))
+        })?;
+
+        let key_file: KeyFile = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&contents).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&contents).map_err(|e| e.to_string())
+        }
+        .map_err(|e| {
+            TurnkeyError::OtherError(format!("Invalid key file {}: {}", path.display(), e))
+        })?;
+
+        let example_key_info = key_file
+            .example_key
+            .map(KeyInfo::from_key_file_key)
+            .transpose()?;
+
+        Ok(Self::from_parts(
+            key_file.organization_id,
+            ApiKeyStamper::new(key_file.api_public_key, key_file.api_private_key)?,
+            example_key_info,
+        ))
+    }
+
+    /// Creates a new instance of the Turnkey client from a decrypted secret bundle.
+    ///
+    /// The first API key pair of the bundle becomes the primary key and any others are
+    /// registered as backups, as with `with_backup_api_key`. Signing keys are registered by
+    /// the label of their `KeySelector`; see `SecretBundle` for the format.
+    ///
+    /// # Arguments
+    ///
+    /// * `bundle` - The deserialized secret bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if the bundle has no API keys or registers a key
+    /// under an unknown label.
+    pub fn from_secret_bundle(bundle: SecretBundle) -> TurnkeyResult<Self> {
+        let mut api_keys = bundle
+            .api_keys
+            .into_iter()
+            .map(|api_key| ApiKeyStamper::new(api_key.public_key, api_key.private_key))
+            .collect::<TurnkeyResult<Vec<_>>>()?
+            .into_iter();
+        let primary_api_key = api_keys
+            .next()
+            .ok_or_else(|| TurnkeyError::OtherError("Secret bundle has no API keys".into()))?;
+
+        let mut example_key_info = None;
+        for (label, key) in bundle.keys {
+            let key_info = KeyInfo::from_key_file_key(key)?;
+            match label.as_str() {
+                "example" => example_key_info = Some(key_info),
+                // add more labels here as the number of keys you're managing for signing grows
+                _ => {
+                    return Err(TurnkeyError::OtherError(format!(
+                        "Unknown key label {} in secret bundle",
+                        label
+                    )))
+                }
+            }
+        }
+
+        let mut turnkey_client =
+            Self::from_parts(bundle.organization_id, primary_api_key, example_key_info);
+        turnkey_client.api_keys.extend(api_keys);
+
+        Ok(turnkey_client)
+    }
+
+    /// Creates a client with the given credentials and default settings.
+    fn from_parts(
+        organization_id: String,
+        api_key: ApiKeyStamper,
+        example_key_info: Option<KeyInfo>,
+    ) -> Self {
+        Self {
+            api_keys: vec![api_key],
+            organization_id,
+            example_key_info,
+            transport: Arc::new(ReqwestTransport::default()),
+            request_limiter: None,
+            stamper: None,
+            api_version: ApiVersion::default(),
+            activity_polling: None,
+            escalation_policy: Arc::new(|_, _| EscalationDecision::Abort),
+            signature_policy: SignaturePolicy::default(),
+            verify_activity_type: false,
+            retry_policy: RetryPolicy::default(),
+            max_stamp_age: Duration::from_secs(60),
+            stamp_logging: StampLogging::default(),
+            batch_concurrency: 4,
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+            max_response_size: None,
+            headers: HeaderMap::from_iter([(
+                USER_AGENT,
+                HeaderValue::from_static(DEFAULT_USER_AGENT),
+            )]),
+            commitment: CommitmentConfig::confirmed(),
+            confirmation_timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+        }
+    }
+
+    /// Sets the commitment level used by the RPC helpers when confirming transactions.
+    ///
+    /// Defaults to `CommitmentConfig::confirmed()`. Use `CommitmentConfig::finalized()`
+    /// for high-value transactions that must not be rolled back.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use {solana_sdk::commitment_config::CommitmentConfig, turnkey::client::Turnkey};
+    ///
+    /// let turnkey_client = Turnkey::new()
+    ///     .unwrap()
+    ///     .with_commitment(CommitmentConfig::finalized());
+    /// ```
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Sets how long the RPC helpers wait for a sent transaction to be confirmed.
+    ///
+    /// Defaults to 120 seconds. This bounds the wait for transactions that are dropped
+    /// without their blockhash expiring or their durable nonce advancing.
+    ///
+    /// # Arguments
+    ///
+    /// * `confirmation_timeout` - The maximum time to wait for a transaction's confirmation.
+    ///
+    pub fn with_confirmation_timeout(mut self, confirmation_timeout: Duration) -> Self {
+        self.confirmation_timeout = confirmation_timeout;
+        self
+    }
+
+    /// Registers a backup API key pair to fail over to.
+    ///
+    /// Requests are stamped with the primary API key first. If the Turnkey API rejects a
+    /// request with an authentication failure (HTTP 401 or 403), for example because the
+    /// key has been disabled, the request is stamped again with the next registered key.
+    /// Backup keys are tried in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_public_key` - The public half of the backup API key.
+    /// * `api_private_key` - The hex-encoded private half of the backup API key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if `api_private_key` is not a hex-encoded 32-byte
+    /// P256 private key.
+    pub fn with_backup_api_key(
+        mut self,
+        api_public_key: impl Into<String>,
+        api_private_key: impl Into<String>,
+    ) -> TurnkeyResult<Self> {
+        // backup keys are stamped the same way as the primary key
+        let primary_api_key = &self.api_keys[0];
+        let backup_api_key = ApiKeyStamper::new(api_public_key, api_private_key)?
+            .with_scheme(primary_api_key.scheme.clone())
+            .with_base64_engine(primary_api_key.engine.clone());
+        self.api_keys.push(backup_api_key);
+        Ok(self)
+    }
+
+    /// Sends requests to the Turnkey API with a pre-built `reqwest::Client`.
+    ///
+    /// This is how proxies, custom root certificates and other TLS settings are configured,
+    /// and it lets the client share a connection pool with the rest of the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The `reqwest::Client` to send all requests with.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use {
+    ///     reqwest::{Certificate, Client, Proxy},
+    ///     turnkey::client::Turnkey,
+    /// };
+    ///
+    /// let root_ca = Certificate::from_pem(&std::fs::read("corporate-ca.pem").unwrap()).unwrap();
+    /// let http_client = Client::builder()
+    ///     .proxy(Proxy::https("https://proxy.internal:3128").unwrap())
+    ///     .add_root_certificate(root_ca)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let turnkey_client = Turnkey::new().unwrap().with_http_client(http_client);
+    /// ```
+    pub fn with_http_client(self, client: reqwest::Client) -> Self {
+        self.with_transport(ReqwestTransport::new(client))
+    }
+
+    /// Replaces the transport used to send requests to the Turnkey API.
+    ///
+    /// By default requests are sent with a `ReqwestTransport`. A custom transport can be
+    /// injected to route requests through a different HTTP stack, or to return canned
+    /// responses in tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - The `TurnkeyTransport` implementation to use for all requests.
+    ///
+    pub fn with_transport(mut self, transport: impl TurnkeyTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Bounds the number of signing requests that may be in flight at the same time.
+    ///
+    /// Each signing request waits for a free slot before it is sent to the Turnkey API,
+    /// which keeps bursts of concurrent `sign_transaction` calls within Turnkey's rate
+    /// limits. By default the number of in-flight requests is unlimited.
+    ///
+    /// The limit is clamped to at least 1, so that signing never waits forever, and to at
+    /// most `Semaphore::MAX_PERMITS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_concurrent_requests` - The maximum number of signing requests sent concurrently.
+    ///
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        let permits = max_concurrent_requests.clamp(1, Semaphore::MAX_PERMITS);
+        self.request_limiter = Some(Arc::new(Semaphore::new(permits)));
+        self
+    }
+
+    /// Sets the base64 engine used to encode the `X-Stamp` header.
+    ///
+    /// Defaults to `base64::engine::general_purpose::URL_SAFE_NO_PAD`, which is the
+    /// encoding the Turnkey API expects.
+    ///
+    /// # Arguments
+    ///
+    /// * `engine` - The `base64` engine used to encode the serialized stamp.
+    ///
+    pub fn with_base64_engine(mut self, engine: GeneralPurpose) -> Self {
+        for api_key in &mut self.api_keys {
+            api_key.engine = engine.clone();
+        }
+        self
+    }
+
+    /// Sets the signature scheme reported in the `X-Stamp` header.
+    ///
+    /// Defaults to `SIGNATURE_SCHEME_TK_API_P256`, the scheme of P256 API keys. The stamp
+    /// itself is always produced with the configured API key; only the reported scheme
+    /// changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - The Turnkey signature scheme identifier.
+    ///
+    pub fn with_signature_scheme(mut self, scheme: impl Into<String>) -> Self {
+        let scheme = scheme.into();
+        for api_key in &mut self.api_keys {
+            api_key.scheme = scheme.clone();
+        }
+        self
+    }
+
+    /// Stamps requests with a custom `Stamper` instead of the configured API keys.
+    ///
+    /// Use a `WebauthnStamper` to authorize requests with a passkey. Requests stamped this
+    /// way are not failed over to backup API keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `stamper` - The `Stamper` used to stamp every request.
+    ///
+    pub fn with_stamper(mut self, stamper: impl Stamper + 'static) -> Self {
+        self.stamper = Some(Arc::new(stamper));
+        self
+    }
+
+    /// Sets the API version whose field names are used when serializing requests.
+    ///
+    /// Defaults to `ApiVersion::V1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_version` - The `ApiVersion` naming scheme to serialize request bodies with.
+    ///
+    pub fn with_api_version(mut self, api_version: ApiVersion) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    /// Enables waiting for pending activities when signing.
+    ///
+    /// Without polling, a signing activity that is not completed immediately (for example
+    /// one awaiting consensus approvals) results in an error. With polling enabled, the
+    /// client re-queries the activity every `interval` until it completes, consulting the
+    /// escalation policy each time `timeout` elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - How long to wait between queries of the activity.
+    /// * `timeout` - How long to wait before invoking the escalation policy.
+    ///
+    pub fn with_activity_polling(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.activity_polling = Some(ActivityPolling { interval, timeout });
+        self
+    }
+
+    /// Sets the escalation policy invoked when a pending activity times out.
+    ///
+    /// The policy is called with the latest state of the activity and the number of
+    /// timeouts so far, and decides whether to keep polling, abort, or reject the
+    /// activity. The default policy aborts on the first timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use {
+    ///     std::time::Duration,
+    ///     turnkey::{client::Turnkey, polling::EscalationDecision},
+    /// };
+    ///
+    /// let turnkey_client = Turnkey::new()
+    ///     .unwrap()
+    ///     .with_activity_polling(Duration::from_secs(2), Duration::from_secs(300))
+    ///     .with_escalation_policy(|activity, timeouts| {
+    ///         if timeouts < 3 {
+    ///             println!("Activity {} is still awaiting approvals", activity.id);
+    ///             EscalationDecision::KeepPolling
+    ///         } else {
+    ///             EscalationDecision::Reject
+    ///         }
+    ///     });
+    /// ```
+    pub fn with_escalation_policy(
+        mut self,
+        policy: impl Fn(&Activity, u32) -> EscalationDecision + Send + Sync + 'static,
+    ) -> Self {
+        self.escalation_policy = Arc::new(policy);
+        self
+    }
+
+    /// Sets what `sign_transaction` does when the key's signature slot is already signed.
+    ///
+    /// Defaults to `SignaturePolicy::Overwrite`, which always re-signs. Use
+    /// `SignaturePolicy::SkipIfPresent` to make re-signing idempotent, or
+    /// `SignaturePolicy::ErrorIfPresent` to treat it as a bug.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy to apply to already-signed slots.
+    ///
+    pub fn with_signature_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signature_policy = policy;
+        self
+    }
+
+    /// Enables or disables verification of the activity type of API responses.
+    ///
+    /// When enabled, every activity returned for a submitted request must have the same
+    /// activity type as the request, e.g. `ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2`, and responses
+    /// of any other type are rejected with an error. Verification is disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to verify response activity types.
+    ///
+    pub fn with_activity_type_verification(mut self, enabled: bool) -> Self {
+        self.verify_activity_type = enabled;
+        self
+    }
+
+    /// Sets the default retry and timeout policy for requests to the Turnkey API.
+    ///
+    /// By default requests are neither retried nor timed out. Individual signing calls can
+    /// override this policy with the `_with_retry_policy` variants of the signing methods,
+    /// e.g. `sign_transaction_with_retry_policy` or `sign_transactions_with_retry_policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The `RetryPolicy` to apply to every request.
+    ///
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sets how old a signing request may grow before it is sent.
+    ///
+    /// Turnkey rejects activities whose timestamp is too old. A signing request that waits
+    /// longer than `max_stamp_age` before being sent, e.g. for a permit when the number of
+    /// concurrent requests is limited, or that is retried after the limit has passed, is
+    /// re-stamped with the current time instead. Requests signed with a caller-supplied
+    /// timestamp via `sign_transaction_at` are never re-stamped; a warning is logged if they
+    /// are stale when sent. Defaults to 60 seconds.
+    ///
+    /// Only signing requests are re-stamped. Other activity submissions, such as
+    /// `create_wallet` or `import_private_key`, are retried with their original stamp, since
+    /// a fresh timestamp would make Turnkey treat a retry as a new, duplicate activity.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_stamp_age` - The maximum age of a request's timestamp when it is sent.
+    ///
+    pub fn with_max_stamp_age(mut self, max_stamp_age: Duration) -> Self {
+        self.max_stamp_age = max_stamp_age;
+        self
+    }
+
+    /// Sets how much of each request's X-Stamp header is included in debug logs.
+    ///
+    /// Requests are logged with `tracing` at the debug level. Stamps are omitted from these
+    /// logs unless configured otherwise here.
+    ///
+    /// # Arguments
+    ///
+    /// * `stamp_logging` - The `StampLogging` level to apply.
+    ///
+    pub fn with_stamp_logging(mut self, stamp_logging: StampLogging) -> Self {
+        self.stamp_logging = stamp_logging;
+        self
+    }
+
+    /// Sets how many transactions `sign_transactions` signs concurrently.
+    ///
+    /// Defaults to 4. This bounds a single batch only; use `with_max_concurrent_requests` to
+    /// bound all signing requests made by the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_concurrency` - The maximum number of transactions of a batch signed at once.
+    ///
+    pub fn with_batch_concurrency(mut self, batch_concurrency: usize) -> Self {
+        self.batch_concurrency = batch_concurrency.max(1);
+        self
+    }
+
+    /// Sets the maximum size of a payload sent for signing.
+    ///
+    /// Defaults to 64 KiB, which fits any Solana transaction or off-chain message. Larger
+    /// payloads are rejected with `TurnkeyError::PayloadTooLarge` before the request is
+    /// built, rather than being hex-encoded in memory and rejected by the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_payload_size` - The maximum payload size in bytes.
+    ///
+    pub fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = max_payload_size;
+        self
+    }
+
+    /// Sets the maximum size of a response body the client deserializes.
+    ///
+    /// By default response bodies of any size are accepted. Larger responses are rejected
+    /// with `TurnkeyError::UnexpectedResponse` instead of being deserialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_response_size` - The maximum response body size in bytes.
+    ///
+    pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = Some(max_response_size);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to `turnkey-rs/<version>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_agent` - The `User-Agent` to identify the client with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if `user_agent` is not a valid header value.
+    pub fn with_user_agent(self, user_agent: &str) -> TurnkeyResult<Self> {
+        self.with_header(USER_AGENT.as_str(), user_agent)
+    }
+
+    /// Adds a static header sent with every request, e.g. a tracing header.
+    ///
+    /// Setting a header that was already set replaces its value. The `Content-Type` and stamp
+    /// headers are always set by the client and cannot be overridden.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header.
+    /// * `value` - The value of the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if `name` is not a valid header name or `value` is
+    /// not a valid header value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use turnkey::client::Turnkey;
+    ///
+    /// let turnkey_client = Turnkey::new()
+    ///     .unwrap()
+    ///     .with_user_agent("payments-service/1.4.0")
+    ///     .unwrap()
+    ///     .with_header("X-Trace-Source", "payments-service")
+    ///     .unwrap();
+    /// ```
+    pub fn with_header(mut self, name: &str, value: &str) -> TurnkeyResult<Self> {
+        let name = HeaderName::from_str(name).map_err(|e| {
+            TurnkeyError::OtherError(format!("Invalid header name {}: {}", name, e))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|e| {
+            TurnkeyError::OtherError(format!("Invalid value for header {}: {}", name, e))
+        })?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Returns the commitment level used by the RPC helpers.
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    /// Returns how long the RPC helpers wait for a sent transaction to be confirmed.
+    pub fn confirmation_timeout(&self) -> Duration {
+        self.confirmation_timeout
+    }
+
+    /// Returns the ID of the organization the client acts in.
+    pub fn organization_id(&self) -> &str {
+        &self.organization_id
+    }
+
+    /// Returns the public half of the primary API key.
+    pub fn api_public_key(&self) -> &str {
+        self.api_keys[0].public_key()
+    }
+
+    /// Returns the Solana public key of the selected signing key.
+    ///
+    /// This is useful e.g. to set the fee payer of a transaction that is signed with the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - The `KeySelector` variant of the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if no key is configured for the selector.
+    pub fn public_key(&self, selector: KeySelector) -> TurnkeyResult<Pubkey> {
+        Ok(self.select_key(selector)?.public_key)
+    }
+
+    /// Returns the client's non-secret configuration for health and status reporting.
+    ///
+    /// The returned `PublicStatus` contains the organization ID, the API base URL and the
+    /// labels of the registered signing keys. Secrets are never included.
+    pub fn public_status(&self) -> PublicStatus {
+        let registered_keys = self.registered_keys();
+
+        PublicStatus {
+            organization_id: self.organization_id.clone(),
+            base_url: TURNKEY_API_URL.to_string(),
+            key_labels: registered_keys
+                .iter()
+                .map(|(selector, _)| selector.label().to_string())
+                .collect(),
+            key_count: registered_keys.len(),
+            api_key_count: self.api_keys.len(),
+        }
+    }
+
+    /// Returns every signing key registered with the client, along with its selector.
+    fn registered_keys(&self) -> Vec<(KeySelector, &KeyInfo)> {
+        [
+            (KeySelector::ExampleKey, self.example_key_info.as_ref()),
+            // add more variants here as the number of keys you're managing for signing grows
+        ]
+        .into_iter()
+        .filter_map(|(selector, key_info)| key_info.map(|key_info| (selector, key_info)))
+        .collect()
+    }
+
+    /// Retrieves the key information associated with the specified `KeySelector`.
+    ///
+    /// Returns the key information, including the private key ID and the public key,
+    /// based on the specified selector. Each `KeySelector` variant corresponds to a
+    /// distinct key pair within the `Turnkey` structure, facilitating access to
+    /// specific cryptographic keys as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - The `KeySelector` variant indicating the key information to retrieve.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if no key is configured for the selector.
+    fn select_key(&self, selector: KeySelector) -> TurnkeyResult<&KeyInfo> {
+        let key_info = match selector {
+            KeySelector::ExampleKey => self.example_key_info.as_ref(),
+            // add more variants here as the number of keys you're managing for signing grows
+        };
+
+        key_info.ok_or_else(|| {
+            TurnkeyError::OtherError(format!("Key {} is not configured", selector.label()))
+        })
+    }
+
+    /// Signs a transaction using the specified key information.
+    ///
+    /// Asynchronously signs the provided `transaction` using the private key associated with the
+    /// selected `key_selector`. This method serializes the transaction's message, signs it, and
+    /// then inserts the signature into the transaction at the appropriate index based on the
+    /// public key's position in the transaction's account keys. It returns the signature; the
+    /// signed transaction is the one the caller passed in.
+    ///
+    /// The method ensures that the specified key for signing is part of the transaction's account
+    /// keys, thereby validating the transaction's integrity and authorization. If the key's slot
+    /// is already signed, the client's `SignaturePolicy` decides whether to re-sign, skip or fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction. The variant determines the set of key information (private and
+    ///   public keys) used in the signing process.
+    ///
+    pub async fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        self.sign_transaction_with_options(transaction, key_selector, SignOptions::default())
+            .await
+    }
+
+    /// Signs a transaction with a caller-supplied activity timestamp.
+    ///
+    /// Behaves exactly like `sign_transaction`, but stamps the signing request with
+    /// `timestamp_ms` instead of the current time. Because stamps are deterministic, signing
+    /// the same transaction with the same key and timestamp reproduces a byte-for-byte
+    /// identical request, so a retry after a crash or network failure submits the same
+    /// intent rather than a new one.
+    ///
+    /// The timestamp is never refreshed. If it is older than the client's maximum stamp age
+    /// (see `with_max_stamp_age`) when the request is sent, a warning is logged and Turnkey
+    /// may reject the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    /// * `timestamp_ms` - The activity timestamp, in milliseconds since the Unix epoch.
+    ///
+    pub async fn sign_transaction_at(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        timestamp_ms: i64,
+    ) -> TurnkeyResult<Signature> {
+        let options = SignOptions {
+            timestamp_ms: Some(timestamp_ms),
+            ..SignOptions::default()
+        };
+        self.sign_transaction_with_options(transaction, key_selector, options)
+            .await
+    }
+
+    /// Signs a transaction with a retry and timeout policy that applies to this call only.
+    ///
+    /// Behaves exactly like `sign_transaction`, but the signing request is retried and
+    /// timed out according to `retry_policy` instead of the client's default policy, e.g.
+    /// to retry a critical signature more persistently than best-effort ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    /// * `retry_policy` - The `RetryPolicy` to apply to the signing request.
+    ///
+    pub async fn sign_transaction_with_retry_policy(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Signature> {
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        self.sign_transaction_with_options(transaction, key_selector, options)
+            .await
+    }
+
+    /// Signs a transaction in place, applying the client's `SignaturePolicy` first.
+    ///
+    /// The signing request is stamped and retried according to `options`.
+    async fn sign_transaction_with_options(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector.clone())?;
+        if let Some(signature) = self.existing_signature(transaction, key_info)? {
+            return Ok(signature);
+        }
+
+        let (signature, _) = self
+            .sign_transaction_with_activity(transaction, key_selector, options)
+            .await?;
+
+        Ok(signature)
+    }
+
+    /// Signs a transaction and returns a compact audit record of the signing operation.
+    ///
+    /// Behaves exactly like `sign_transaction`, but instead of the signed transaction it
+    /// returns an `AuditRecord` identifying the organization, private key and Turnkey
+    /// activity involved, which can be appended directly to an audit log.
+    ///
+    /// The client's `SignaturePolicy` is applied as in `sign_transaction`. When an already
+    /// signed slot is skipped, no activity is submitted, so the record has no `activity_id`
+    /// and its status is `SIGNATURE_ALREADY_PRESENT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    ///
+    pub async fn sign_transaction_with_audit(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<(Signature, AuditRecord)> {
+        let key_info = self.select_key(key_selector.clone())?;
+        let private_key_id = key_info.private_key_id.clone();
+        let message_hex_len = transaction.message_data().len() * 2;
+
+        let (signature, activity_id, status) =
+            match self.existing_signature(transaction, key_info)? {
+                Some(signature) => (signature, None, SIGNATURE_ALREADY_PRESENT.to_string()),
+                None => {
+                    let (signature, activity) = self
+                        .sign_transaction_with_activity(
+                            transaction,
+                            key_selector,
+                            SignOptions::default(),
+                        )
+                        .await?;
+                    (signature, Some(activity.id), activity.status)
+                }
+            };
+
+        let audit_record = AuditRecord {
+            timestamp: chrono::Utc::now(),
+            organization_id: self.organization_id.clone(),
+            private_key_id,
+            activity_id,
+            status,
+            signature_base58: signature.to_string(),
+            message_hex_len,
+        };
+
+        Ok((signature, audit_record))
+    }
+
+    /// Signs a transaction in place and returns the signature with the completed activity.
+    async fn sign_transaction_with_activity(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<(Signature, Activity)> {
+        let key_info = self.select_key(key_selector)?;
+        let serialized_message = transaction.message_data();
+
+        // get signature
+        let (components, activity) = self
+            .sign_components_with_activity(&serialized_message, key_info, options)
+            .await?;
+        let signature = Signature::try_from(components.signature_bytes.as_slice())?;
+
+        // add signature to transaction
+        match required_signer_index(transaction, &key_info.public_key) {
+            Some(i) => {
+                ensure_signature_slots(transaction);
+                transaction.signatures[i] = signature;
+                Ok((signature, activity))
+            }
+            None => Err(TurnkeyError::OtherError(format!(
+                "Key {} is not a required signer",
+                key_info.public_key
+            ))),
+        }
+    }
+
+    /// Signs a transaction as its fee payer, using whichever registered key is the fee payer.
+    ///
+    /// Looks up the transaction's fee payer among the client's registered keys and signs the
+    /// fee payer's slot with `sign_transaction`, so the caller doesn't need to know which
+    /// `KeySelector` it corresponds to.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the fee payer's signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if the transaction has no fee payer or no registered
+    /// key matches it.
+    pub async fn sign_as_fee_payer_auto(
+        &self,
+        transaction: &mut Transaction,
+    ) -> TurnkeyResult<Signature> {
+        let fee_payer = fee_payer(transaction)
+            .ok_or_else(|| TurnkeyError::OtherError("Transaction has no fee payer".into()))?;
+        let key_selector = self
+            .registered_keys()
+            .into_iter()
+            .find(|(_, key_info)| key_info.public_key == fee_payer)
+            .map(|(selector, _)| selector)
+            .ok_or_else(|| {
+                TurnkeyError::OtherError(format!(
+                    "No registered key matches fee payer {}",
+                    fee_payer
+                ))
+            })?;
+
+        self.sign_transaction(transaction, key_selector).await
+    }
+
+    /// Signs a Solana off-chain message, e.g. for Sign-In With Solana style authentication.
+    ///
+    /// The message is framed as a version 0 off-chain message, with the `\xffsolana offchain`
+    /// signing domain, version, format and length header, and the framed bytes are signed.
+    /// The signature can be checked with `OffchainMessage::verify`.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message content to frame and sign.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if the message is empty or too long to be framed.
+    pub async fn sign_offchain_message(
+        &self,
+        message: &[u8],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector)?;
+        let framed_message = OffchainMessage::new(0, message)
+            .and_then(|offchain_message| offchain_message.serialize())
+            .map_err(|e| TurnkeyError::OtherError(format!("Invalid off-chain message: {}", e)))?;
+
+        let signature_bytes = self
+            .sign_bytes(&framed_message, key_info, SignOptions::default())
+            .await?;
+
+        Ok(Signature::try_from(signature_bytes.as_slice())?)
+    }
+
+    /// Signs a transaction's message without modifying the transaction.
+    ///
+    /// Serializes the message of `transaction`, signs it with the selected key and returns the
+    /// signature without inserting it, e.g. to compare against a locally produced signature.
+    /// Use `sign_transaction` to sign the transaction in place instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction whose message is signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` before anything is signed if the selected key is not
+    /// a required signer of the transaction.
+    pub async fn sign_transaction_detached(
+        &self,
+        transaction: &Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector.clone())?;
+        if required_signer_index(transaction, &key_info.public_key).is_none() {
+            return Err(TurnkeyError::OtherError(format!(
+                "Key {} ({}) is not a required signer",
+                key_selector.label(),
+                key_info.public_key
+            )));
+        }
+
+        let signature_bytes = self
+            .sign_bytes(
+                &transaction.message_data(),
+                key_info,
+                SignOptions::default(),
+            )
+            .await?;
+
+        Ok(Signature::try_from(signature_bytes.as_slice())?)
+    }
+
+    /// Signs a Solana `Message` without wrapping it in a transaction.
+    ///
+    /// Serializes `message`, signs it with the selected key and returns the signature for the
+    /// caller to assemble into a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` before anything is signed if the selected key is not
+    /// a required signer of the message.
+    pub async fn sign_message_struct(
+        &self,
+        message: &Message,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector.clone())?;
+        let is_required_signer = message
+            .account_keys
+            .iter()
+            .position(|key| key == &key_info.public_key)
+            .is_some_and(|i| message.is_signer(i));
+        if !is_required_signer {
+            return Err(TurnkeyError::OtherError(format!(
+                "Key {} ({}) is not a required signer",
+                key_selector.label(),
+                key_info.public_key
+            )));
+        }
+
+        let signature_bytes = self
+            .sign_bytes(&message.serialize(), key_info, SignOptions::default())
+            .await?;
+
+        Ok(Signature::try_from(signature_bytes.as_slice())?)
+    }
+
+    /// Applies the client's `SignaturePolicy` to the signature slot of `key_info`.
+    ///
+    /// Returns the existing signature when signing should be skipped, and `None` when the
+    /// transaction should be signed.
+    fn existing_signature(
+        &self,
+        transaction: &Transaction,
+        key_info: &KeyInfo,
+    ) -> TurnkeyResult<Option<Signature>> {
+        let existing = transaction
+            .message
+            .account_keys
+            .iter()
+            .position(|key| key == &key_info.public_key)
+            .and_then(|i| transaction.signatures.get(i))
+            .filter(|signature| **signature != Signature::default());
+
+        match (self.signature_policy, existing) {
+            (SignaturePolicy::SkipIfPresent, Some(signature)) => Ok(Some(*signature)),
+            (SignaturePolicy::ErrorIfPresent, Some(_)) => Err(TurnkeyError::OtherError(format!(
+                "Signature slot for {} is already signed",
+                key_info.public_key
+            ))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Signs a batch of independent transactions with the same key.
+    ///
+    /// Up to the client's batch concurrency (see `with_batch_concurrency`) transactions are
+    /// signed at once, and the signatures are returned in the same order as `transactions`.
+    ///
+    /// The batch fails fast: the first error, in input order, is returned and no further
+    /// transactions are signed, although transactions earlier in the batch, and some that were
+    /// in flight, may already have been signed in place. Use `sign_transactions_collect` to
+    /// keep signing past failures.
+    ///
+    /// # Arguments
+    ///
+    /// * `transactions` - The transactions to be signed. Each transaction is modified in place
+    ///   by adding its signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transactions.
+    ///
+    pub async fn sign_transactions(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.sign_transactions_with_options(transactions, key_selector, SignOptions::default())
+            .await
+    }
+
+    /// Signs a batch of independent transactions with a retry and timeout policy that
+    /// applies to this call only.
+    ///
+    /// Behaves exactly like `sign_transactions`, but every signing request of the batch is
+    /// retried and timed out according to `retry_policy` instead of the client's default
+    /// policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `transactions` - The transactions to be signed. Each transaction is modified in place
+    ///   by adding its signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transactions.
+    /// * `retry_policy` - The `RetryPolicy` to apply to each signing request.
+    ///
+    pub async fn sign_transactions_with_retry_policy(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        self.sign_transactions_with_options(transactions, key_selector, options)
+            .await
+    }
+
+    /// Signs a batch of transactions, failing fast, with each request sent according to
+    /// `options`.
+    async fn sign_transactions_with_options(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        stream::iter(transactions.iter_mut())
+            .map(|transaction| {
+                self.sign_transaction_with_options(transaction, key_selector.clone(), options)
+            })
+            .buffered(self.batch_concurrency)
+            .try_collect()
+            .await
+    }
+
+    /// Signs a batch of independent transactions with the same key, collecting every result.
+    ///
+    /// Behaves like `sign_transactions`, but signs every transaction regardless of failures
+    /// and returns one result per transaction, in the same order as `transactions`, so that
+    /// a single bad transaction doesn't sink the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `transactions` - The transactions to be signed. Each transaction that is signed
+    ///   successfully is modified in place by adding its signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transactions.
+    ///
+    pub async fn sign_transactions_collect(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+    ) -> Vec<TurnkeyResult<Signature>> {
+        self.sign_transactions_collect_with_options(
+            transactions,
+            key_selector,
+            SignOptions::default(),
+        )
+        .await
+    }
+
+    /// Signs a batch of independent transactions, collecting every result, with a retry and
+    /// timeout policy that applies to this call only.
+    ///
+    /// Behaves exactly like `sign_transactions_collect`, but every signing request of the
+    /// batch is retried and timed out according to `retry_policy` instead of the client's
+    /// default policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `transactions` - The transactions to be signed. Each transaction that is signed
+    ///   successfully is modified in place by adding its signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transactions.
+    /// * `retry_policy` - The `RetryPolicy` to apply to each signing request.
+    ///
+    pub async fn sign_transactions_collect_with_retry_policy(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> Vec<TurnkeyResult<Signature>> {
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        self.sign_transactions_collect_with_options(transactions, key_selector, options)
+            .await
+    }
+
+    /// Signs a batch of transactions, collecting every result, with each request sent
+    /// according to `options`.
+    async fn sign_transactions_collect_with_options(
+        &self,
+        transactions: &mut [Transaction],
+        key_selector: KeySelector,
+        options: SignOptions<'_>,
+    ) -> Vec<TurnkeyResult<Signature>> {
+        stream::iter(transactions.iter_mut())
+            .map(|transaction| {
+                self.sign_transaction_with_options(transaction, key_selector.clone(), options)
+            })
+            .buffered(self.batch_concurrency)
+            .collect()
+            .await
+    }
+
+    /// Signs a transaction with several Turnkey-managed keys.
+    ///
+    /// The transaction's message is serialized once and signed concurrently with the
+    /// private key of every selector. Each signature is inserted at the signature slot of
+    /// the corresponding public key, leaving the slots of any other signers untouched.
+    /// The signatures are returned in the same order as `key_selectors`.
+    ///
+    /// The client's `SignaturePolicy` is applied to every selected key's slot before
+    /// anything is signed: slots skipped under `SignaturePolicy::SkipIfPresent` keep, and
+    /// return, their existing signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signatures.
+    /// * `key_selectors` - The `KeySelector` variants of the keys to sign with.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` before anything is signed if any selected key is
+    /// not a required signer of the transaction, or if any selected key's slot is already
+    /// signed under `SignaturePolicy::ErrorIfPresent`.
+    pub async fn sign_transaction_multi(
+        &self,
+        transaction: &mut Transaction,
+        key_selectors: &[KeySelector],
+    ) -> TurnkeyResult<Vec<Signature>> {
+        self.sign_transaction_multi_with_options(transaction, key_selectors, SignOptions::default())
+            .await
+    }
+
+    /// Signs a transaction with several Turnkey-managed keys, with a retry and timeout
+    /// policy that applies to this call only.
+    ///
+    /// Behaves exactly like `sign_transaction_multi`, but every signing request is retried
+    /// and timed out according to `retry_policy` instead of the client's default policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signatures.
+    /// * `key_selectors` - The `KeySelector` variants of the keys to sign with.
+    /// * `retry_policy` - The `RetryPolicy` to apply to each signing request.
+    ///
+    pub async fn sign_transaction_multi_with_retry_policy(
+        &self,
+        transaction: &mut Transaction,
+        key_selectors: &[KeySelector],
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        self.sign_transaction_multi_with_options(transaction, key_selectors, options)
+            .await
+    }
+
+    /// Signs a transaction with several keys, with each request sent according to `options`.
+    async fn sign_transaction_multi_with_options(
+        &self,
+        transaction: &mut Transaction,
+        key_selectors: &[KeySelector],
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<Vec<Signature>> {
+        let signers = key_selectors
+            .iter()
+            .map(|selector| {
+                let key_info = self.select_key(selector.clone())?;
+                required_signer_index(transaction, &key_info.public_key)
+                    .map(|index| (index, key_info))
+                    .ok_or_else(|| {
+                        TurnkeyError::OtherError(format!(
+                            "Key {} ({}) is not a required signer",
+                            selector.label(),
+                            key_info.public_key
+                        ))
+                    })
+            })
+            .collect::<TurnkeyResult<Vec<_>>>()?;
+        let existing = signers
+            .iter()
+            .map(|(_, key_info)| self.existing_signature(transaction, key_info))
+            .collect::<TurnkeyResult<Vec<_>>>()?;
+
+        let serialized_message = transaction.message_data();
+        let message = serialized_message.as_slice();
+        let signatures = try_join_all(signers.iter().zip(existing).map(
+            |((_, key_info), existing)| async move {
+                if let Some(signature) = existing {
+                    return Ok(signature);
+                }
+                let signature_bytes = self.sign_bytes(message, key_info, options).await?;
+                Ok::<_, TurnkeyError>(Signature::try_from(signature_bytes.as_slice())?)
+            },
+        ))
+        .await?;
+
+        ensure_signature_slots(transaction);
+        for ((index, _), signature) in signers.iter().zip(&signatures) {
+            transaction.signatures[*index] = *signature;
+        }
+
+        Ok(signatures)
+    }
+
+    /// Signs a transaction and returns the signed message data alongside the signature.
+    ///
+    /// Behaves exactly like `sign_transaction`, but additionally returns the serialized
+    /// message bytes that were signed and their runtime message hash. This is useful for
+    /// tracking and deduplicating signed transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    ///
+    pub async fn sign_transaction_with_message(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignedMessage> {
+        let message_data = transaction.message_data();
+        let signature = self.sign_transaction(transaction, key_selector).await?;
+
+        Ok(SignedMessage {
+            signature,
+            message_hash: Message::hash_raw_message(&message_data),
+            message_data,
+        })
+    }
+
+    /// Signs a transaction and returns its serialized bytes from before and after signing.
+    ///
+    /// Behaves exactly like `sign_transaction`, but additionally serializes the transaction
+    /// as it is sent on the wire both before and after the signature is inserted, which is
+    /// useful for debugging signature placement.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    ///
+    pub async fn sign_transaction_with_bytes(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignedTransactionBytes> {
+        let unsigned_bytes = serialize_transaction(transaction)?;
+        let signature = self.sign_transaction(transaction, key_selector).await?;
+
+        Ok(SignedTransactionBytes {
+            unsigned_bytes,
+            signed_bytes: serialize_transaction(transaction)?,
+            signature,
+        })
+    }
+
+    /// Signs a transaction and returns it serialized and encoded for broadcasting.
+    ///
+    /// Behaves exactly like `sign_transaction`, but returns the signed transaction encoded
+    /// with `encoding`, ready to hand to a broadcaster.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
+    ///   is modified in place by adding the signature.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    /// * `encoding` - The `TransactionEncoding` to return the signed transaction in.
+    ///
+    pub async fn sign_transaction_encoded(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        encoding: TransactionEncoding,
+    ) -> TurnkeyResult<String> {
+        self.sign_transaction(transaction, key_selector).await?;
+
+        encode_transaction(transaction, encoding)
+    }
+
+    /// Asynchronously signs a byte array with the specified private key.
+    ///
+    /// This method constructs a request to sign a given payload represented by `bytes` using the
+    /// private key described by `key_info`. It sends this request to the Turnkey API,
+    /// specifying that the payload is in hexadecimal format and that no hash function is applied
+    /// before signing. The method waits for the signing operation to complete and processes the
+    /// response to extract the signature.
+    ///
+    /// The signature process involves creating a digital stamp (`x_stamp`) for the request body,
+    /// sending the request to the Turnkey API's sign raw payload endpoint, and then interpreting
+    /// the response to retrieve the actual signature bytes. If activity polling is enabled and
+    /// the activity is still pending, the method waits for it via `wait_for_activity`. Signatures
+    /// from ed25519 keys must be 64 bytes long and verify against the key's public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte array to be signed, represented as a slice of bytes (`&[u8]`).
+    /// * `key_info` - The key information of the private key to use for signing the payload.
+    /// * `options` - The per-call overrides to stamp and send the signing request with.
+    ///
+    async fn sign_bytes(
+        &self,
+        bytes: &[u8],
+        key_info: &KeyInfo,
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<Vec<u8>> {
+        let (components, _) = self
+            .sign_components_with_activity(bytes, key_info, options)
+            .await?;
+        Ok(components.signature_bytes)
+    }
+
+    /// Signs a byte array and returns the raw signature components.
+    ///
+    /// Unlike the transaction signing methods, this returns the `r`, `s` and, when Turnkey
+    /// provides one, `v` values exactly as reported by the `SIGN_RAW_PAYLOAD` activity, next
+    /// to the assembled signature bytes, e.g. for logging or cross-checking against the
+    /// Turnkey dashboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The payload to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the payload.
+    pub async fn sign_raw_components(
+        &self,
+        bytes: &[u8],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<SignatureComponents> {
+        let key_info = self.select_key(key_selector)?;
+        let (components, _) = self
+            .sign_components_with_activity(bytes, key_info, SignOptions::default())
+            .await?;
+        Ok(components)
+    }
+
+    /// Signs a byte array and returns the raw signature components, with a retry and timeout
+    /// policy that applies to this call only.
+    ///
+    /// Behaves exactly like `sign_raw_components`, but the signing request is retried and
+    /// timed out according to `retry_policy` instead of the client's default policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The payload to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the payload.
+    /// * `retry_policy` - The `RetryPolicy` to apply to the signing request.
+    ///
+    pub async fn sign_raw_components_with_retry_policy(
+        &self,
+        bytes: &[u8],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<SignatureComponents> {
+        let key_info = self.select_key(key_selector)?;
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        let (components, _) = self
+            .sign_components_with_activity(bytes, key_info, options)
+            .await?;
+        Ok(components)
+    }
+
+    /// Signs a SHA-256 digest computed locally, e.g. of a payload too large to send.
+    ///
+    /// Only the 32-byte digest is sent to Turnkey, with `HASH_FUNCTION_NOT_APPLICABLE`, so
+    /// Turnkey signs the digest as-is rather than hashing it again. The signature is therefore
+    /// over the digest, and verifies against `Sha256::digest(payload)` rather than the payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - The SHA-256 digest of the payload to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the digest.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use {
+    ///     sha2::{Digest, Sha256},
+    ///     turnkey::{KeySelector, Turnkey},
+    /// };
+    ///
+    /// # async fn example() {
+    /// let turnkey_client = Turnkey::new().unwrap();
+    /// let digest: [u8; 32] = Sha256::digest(b"large payload").into();
+    ///
+    /// let signature = turnkey_client
+    ///     .sign_digest(&digest, KeySelector::ExampleKey)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn sign_digest(
+        &self,
+        digest: &[u8; 32],
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector)?;
+        let signature_bytes = self
+            .sign_bytes(digest, key_info, SignOptions::default())
+            .await?;
+
+        Ok(Signature::try_from(signature_bytes.as_slice())?)
+    }
+
+    /// Signs a SHA-256 digest computed locally, with a retry and timeout policy that applies
+    /// to this call only.
+    ///
+    /// Behaves exactly like `sign_digest`, but the signing request is retried and timed out
+    /// according to `retry_policy` instead of the client's default policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - The SHA-256 digest of the payload to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the digest.
+    /// * `retry_policy` - The `RetryPolicy` to apply to the signing request.
+    ///
+    pub async fn sign_digest_with_retry_policy(
+        &self,
+        digest: &[u8; 32],
+        key_selector: KeySelector,
+        retry_policy: RetryPolicy,
+    ) -> TurnkeyResult<Signature> {
+        let key_info = self.select_key(key_selector)?;
+        let options = SignOptions::with_retry_policy(&retry_policy);
+        let signature_bytes = self.sign_bytes(digest, key_info, options).await?;
+
+        Ok(Signature::try_from(signature_bytes.as_slice())?)
+    }
+
+    /// Signs a byte array and returns the signature components along with the completed activity.
+    ///
+    /// If `options` carries a timestamp, the activity is stamped with it rather than the
+    /// current time, which makes the request body, and therefore its stamp, deterministic.
+    /// Otherwise the current time is used, and refreshed if the request has grown older than
+    /// the client's maximum stamp age by the time it is sent or retried. The signing request
+    /// is sent according to the retry policy of `options`, or the client's default policy.
+    async fn sign_components_with_activity(
+        &self,
+        bytes: &[u8],
+        key_info: &KeyInfo,
+        options: SignOptions<'_>,
+    ) -> TurnkeyResult<(SignatureComponents, Activity)> {
+        let timestamp_ms = options.timestamp_ms;
+        let retry_policy = options.retry_policy.unwrap_or(&self.retry_policy);
+
+        if bytes.len() > self.max_payload_size {
+            return Err(TurnkeyError::PayloadTooLarge {
+                size: bytes.len(),
+                max_size: self.max_payload_size,
+            });
+        }
+
+        let mut sign_raw_payload_body = SignRawPayloadRequest {
+            activity_type: "ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2".to_string(),
+            timestamp_ms: timestamp_ms
+                .unwrap_or_else(|| chrono::Utc::now().timestamp_millis())
+                .to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: SignRawPayloadIntentV2Parameters {
+                sign_with: key_info.private_key_id.clone(),
+                payload: bytes_to_hex(bytes).map_err(TurnkeyError::hex)?,
+                encoding: "PAYLOAD_ENCODING_HEXADECIMAL".to_string(),
+                hash_function: "HASH_FUNCTION_NOT_APPLICABLE".to_string(),
+            },
+        };
+
+        // hold a permit for the duration of the request when concurrency is bounded
+        let permit = self.acquire_request_permit().await?;
+
+        // re-stamp with a fresh timestamp if the request went stale waiting for a permit,
+        // but never replace a caller-supplied timestamp
+        if self.is_stale(&sign_raw_payload_body.timestamp_ms) {
+            match timestamp_ms {
+                None => {
+                    sign_raw_payload_body.timestamp_ms =
+                        chrono::Utc::now().timestamp_millis().to_string();
+                }
+                Some(timestamp_ms) => tracing::warn!(
+                    timestamp_ms,
+                    max_stamp_age = ?self.max_stamp_age,
+                    "Sending signing request with a stale caller-supplied timestamp"
+                ),
+            }
+        }
+
+        let response_body: ActivityResponse = self
+            .post_with_retry_policy(
+                "/public/v1/submit/sign_raw_payload",
+                &sign_raw_payload_body,
+                retry_policy,
+                timestamp_ms.is_none(),
+            )
+            .await?;
+        drop(permit);
+
+        let activity = self
+            .complete_activity(response_body, &sign_raw_payload_body.activity_type)
+            .await?;
+
+        let result = activity
+            .result
+            .as_ref()
+            .and_then(|result| result.sign_raw_payload_result.as_ref());
+        if let Some(result) = result {
+            let signature_bytes = assemble_signature(&result.r, &result.s)?;
+            if key_info.curve == Curve::Ed25519 {
+                verify_ed25519_signature(&signature_bytes, &key_info.public_key, bytes)?;
+            }
+
+            let components = SignatureComponents {
+                r: result.r.clone(),
+                s: result.s.clone(),
+                v: result.v.clone(),
+                signature_bytes,
+            };
+            return Ok((components, activity));
+        }
+
+        Err(
+            TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                || TurnkeyError::OtherError("Missing SIGN_RAW_PAYLOAD result".into()),
+            ),
+        )
+    }
+
+    /// Creates a new wallet and derives the given accounts in it.
+    ///
+    /// Submits a `CREATE_WALLET` activity and returns the ID of the new wallet together with
+    /// the addresses of its accounts, in the same order as `accounts`. Use
+    /// `WalletAccountParams::solana` to derive Solana accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The human-readable name of the wallet.
+    /// * `accounts` - The derivation parameters of the accounts to create in the wallet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the activity's status error, e.g. `TurnkeyError::ConsensusNeeded`, if the
+    /// wallet was not created immediately and activity polling is disabled.
+    pub async fn create_wallet(
+        &self,
+        name: &str,
+        accounts: &[WalletAccountParams],
+    ) -> TurnkeyResult<CreateWalletResult> {
+        let create_wallet_body = CreateWalletRequest {
+            activity_type: "ACTIVITY_TYPE_CREATE_WALLET".to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: CreateWalletIntentParameters {
+                wallet_name: name.to_string(),
+                accounts: accounts.to_vec(),
+            },
+        };
+
+        let response_body: ActivityResponse = self
+            .post("/public/v1/submit/create_wallet", &create_wallet_body)
+            .await?;
+        let activity = self
+            .complete_activity(response_body, &create_wallet_body.activity_type)
+            .await?;
 
-/// Enumerates the selectable keys for operations, distinguishing by their use case.
-pub enum KeySelector {
-    ExampleKey,
-    // other key info variants depending on what other keys you need to sign with
-}
+        match activity
+            .result
+            .and_then(|result| result.create_wallet_result)
+        {
+            Some(result) => Ok(result),
+            None => Err(
+                TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                    || TurnkeyError::OtherError("Missing CREATE_WALLET result".into()),
+                ),
+            ),
+        }
+    }
 
-impl Turnkey {
-    /// Creates a new instance of the Turnkey client.
+    /// Creates a new sub-organization of the client's organization.
     ///
-    /// # Examples
+    /// Submits a `CREATE_SUB_ORGANIZATION_V7` activity and returns the ID of the new
+    /// sub-organization, the IDs of its root users and, if `params` specifies a wallet, the
+    /// wallet's ID and addresses.
     ///
-    /// ```
-    /// use turnkey::client::Turnkey;
+    /// # Arguments
     ///
-    /// let turnkey_client = Turnkey::new();
-    /// ```
-    pub fn new() -> TurnkeyResult<Self> {
-        dotenv().ok();
+    /// * `params` - The name, root users, root quorum threshold and optional wallet of the
+    ///   sub-organization.
+    ///
+    /// # Errors
+    ///
+    /// Returns the activity's status error, e.g. `TurnkeyError::ConsensusNeeded`, if the
+    /// sub-organization was not created immediately and activity polling is disabled.
+    pub async fn create_sub_organization(
+        &self,
+        params: CreateSubOrgParams,
+    ) -> TurnkeyResult<CreateSubOrgResult> {
+        let create_sub_org_body = CreateSubOrgRequest {
+            activity_type: "ACTIVITY_TYPE_CREATE_SUB_ORGANIZATION_V7".to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: params,
+        };
 
-        Ok(Self {
-            api_public_key: env::var("TURNKEY_API_PUBLIC_KEY")?,
-            api_private_key: env::var("TURNKEY_API_PRIVATE_KEY")?,
-            organization_id: env::var("TURNKEY_ORGANIZATION_ID")?,
-            example_key_info: KeyInfo {
-                private_key_id: env::var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID")?,
-                public_key: Pubkey::from_str(&env::var("TURNKEY_EXAMPLE_PUBLIC_KEY")?)?,
-            },
-            client: Client::new(),
-        })
+        let response_body: ActivityResponse = self
+            .post(
+                "/public/v1/submit/create_sub_organization",
+                &create_sub_org_body,
+            )
+            .await?;
+        let activity = self
+            .complete_activity(response_body, &create_sub_org_body.activity_type)
+            .await?;
+
+        match activity
+            .result
+            .and_then(|result| result.create_sub_organization_result_v7)
+        {
+            Some(result) => Ok(result),
+            None => Err(
+                TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                    || TurnkeyError::OtherError("Missing CREATE_SUB_ORGANIZATION result".into()),
+                ),
+            ),
+        }
     }
 
-    /// Retrieves the key information associated with the specified `KeySelector`.
+    /// Exports a private key, encrypted to a target public key.
     ///
-    /// Returns the key information, including the private key ID and the public key,
-    /// based on the specified selector. Each `KeySelector` variant corresponds to a
-    /// distinct key pair within the `Turnkey` structure, facilitating access to
-    /// specific cryptographic keys as needed.
+    /// Submits an `EXPORT_PRIVATE_KEY` activity and returns the export bundle, which Turnkey
+    /// encrypts with HPKE to `target_public_key`. The bundle is returned as-is; decrypting
+    /// it with the target private key is left to the caller.
     ///
     /// # Arguments
     ///
-    /// * `selector` - The `KeySelector` variant indicating the key information to retrieve.
-    fn select_key(&self, selector: KeySelector) -> &KeyInfo {
-        match selector {
-            KeySelector::ExampleKey => &self.example_key_info,
-            // add more variants here as the number of keys you're managing for signing grows
+    /// * `private_key_id` - The identifier of the private key to export.
+    /// * `target_public_key` - The hex-encoded, uncompressed P256 public key to encrypt the
+    ///   export bundle to.
+    ///
+    /// # Errors
+    ///
+    /// Returns the activity's status error, e.g. `TurnkeyError::ConsensusNeeded`, if the
+    /// key was not exported immediately and activity polling is disabled.
+    pub async fn export_private_key(
+        &self,
+        private_key_id: &str,
+        target_public_key: &str,
+    ) -> TurnkeyResult<ExportResult> {
+        let export_private_key_body = ExportPrivateKeyRequest {
+            activity_type: "ACTIVITY_TYPE_EXPORT_PRIVATE_KEY".to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: ExportPrivateKeyIntentParameters {
+                private_key_id: private_key_id.to_string(),
+                target_public_key: target_public_key.to_string(),
+            },
+        };
+
+        let response_body: ActivityResponse = self
+            .post(
+                "/public/v1/submit/export_private_key",
+                &export_private_key_body,
+            )
+            .await?;
+        let activity = self
+            .complete_activity(response_body, &export_private_key_body.activity_type)
+            .await?;
+
+        match activity
+            .result
+            .and_then(|result| result.export_private_key_result)
+        {
+            Some(result) => Ok(result),
+            None => Err(
+                TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                    || TurnkeyError::OtherError("Missing EXPORT_PRIVATE_KEY result".into()),
+                ),
+            ),
+        }
+    }
+
+    /// Starts a private key import and returns Turnkey's import bundle.
+    ///
+    /// Submits an `INIT_IMPORT_PRIVATE_KEY` activity for the user the client's API key
+    /// belongs to. The returned bundle holds the target public key that the key material must
+    /// be encrypted to with HPKE; the encrypted bundle is then passed to `import_private_key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the activity's status error, e.g. `TurnkeyError::ConsensusNeeded`, if the
+    /// import was not initialized immediately and activity polling is disabled.
+    pub async fn init_import_private_key(&self) -> TurnkeyResult<String> {
+        let user_id = self.whoami().await?.user_id;
+        let init_import_body = InitImportPrivateKeyRequest {
+            activity_type: "ACTIVITY_TYPE_INIT_IMPORT_PRIVATE_KEY".to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: InitImportPrivateKeyIntentParameters { user_id },
+        };
+
+        let response_body: ActivityResponse = self
+            .post(
+                "/public/v1/submit/init_import_private_key",
+                &init_import_body,
+            )
+            .await?;
+        let activity = self
+            .complete_activity(response_body, &init_import_body.activity_type)
+            .await?;
+
+        match activity
+            .result
+            .and_then(|result| result.init_import_private_key_result)
+        {
+            Some(result) => Ok(result.import_bundle),
+            None => Err(
+                TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                    || TurnkeyError::OtherError("Missing INIT_IMPORT_PRIVATE_KEY result".into()),
+                ),
+            ),
         }
     }
 
-    /// Creates a digital stamp for a given message.
+    /// Imports an existing private key into Turnkey.
+    ///
+    /// Submits an `IMPORT_PRIVATE_KEY` activity on behalf of the user the client's API key
+    /// belongs to, and returns the ID of the new Turnkey private key with its addresses.
+    /// Ed25519 keys get a Solana address.
+    ///
+    /// # Arguments
+    ///
+    /// * `encrypted_bundle` - The key material, encrypted with HPKE to the target key of the
+    ///   import bundle returned by `init_import_private_key`.
+    /// * `name` - The human-readable name of the imported key.
+    /// * `curve` - The curve of the imported key.
     ///
-    /// This method signs a given message with a private API key, generates a
-    /// signature, and constructs a digital stamp containing the signature,
-    /// the public API key, and the signature scheme. The digital stamp is
-    /// then serialized, base64-url encoded, and returned.
+    /// # Errors
+    ///
+    /// Returns the activity's status error, e.g. `TurnkeyError::ConsensusNeeded`, if the
+    /// key was not imported immediately and activity polling is disabled.
+    pub async fn import_private_key(
+        &self,
+        encrypted_bundle: &str,
+        name: &str,
+        curve: Curve,
+    ) -> TurnkeyResult<ImportResult> {
+        let user_id = self.whoami().await?.user_id;
+        let import_private_key_body = ImportPrivateKeyRequest {
+            activity_type: "ACTIVITY_TYPE_IMPORT_PRIVATE_KEY".to_string(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
+            organization_id: self.organization_id.clone(),
+            parameters: ImportPrivateKeyIntentParameters {
+                user_id,
+                private_key_name: name.to_string(),
+                encrypted_bundle: encrypted_bundle.to_string(),
+                curve: curve.api_name().to_string(),
+                address_formats: curve.address_formats(),
+            },
+        };
+
+        let response_body: ActivityResponse = self
+            .post(
+                "/public/v1/submit/import_private_key",
+                &import_private_key_body,
+            )
+            .await?;
+        let activity = self
+            .complete_activity(response_body, &import_private_key_body.activity_type)
+            .await?;
+
+        match activity
+            .result
+            .and_then(|result| result.import_private_key_result)
+        {
+            Some(result) => Ok(result),
+            None => Err(
+                TurnkeyError::from_activity_status(&activity.id, &activity.status).unwrap_or_else(
+                    || TurnkeyError::OtherError("Missing IMPORT_PRIVATE_KEY result".into()),
+                ),
+            ),
+        }
+    }
+
+    /// Waits for a submitted activity to complete if polling is enabled, and verifies its type.
     ///
     /// # Arguments
     ///
-    /// * `message` - The message to be signed and stamped.
+    /// * `response_body` - The response to the activity's submission.
+    /// * `activity_type` - The activity type of the submitted request.
     ///
-    fn stamp(&self, message: &str) -> TurnkeyResult<String> {
-        let private_api_key_bytes = hex_to_bytes(&self.api_private_key)?;
-        let signing_key = SigningKey::from_bytes(&private_api_key_bytes)?;
+    async fn complete_activity(
+        &self,
+        mut response_body: ActivityResponse,
+        activity_type: &str,
+    ) -> TurnkeyResult<Activity> {
+        let status = response_body.activity.status.as_str();
+        if self.activity_polling.is_some() && PENDING_ACTIVITY_STATUSES.contains(&status) {
+            response_body = self.wait_for_activity(&response_body.activity.id).await?;
+        }
 
-        let signature = signing_key.sign(message.as_bytes());
-        let signature_der = signature.to_der().to_bytes();
-        let signature_hex = bytes_to_hex(&signature_der)?;
+        self.verify_activity_type(&response_body.activity, activity_type)?;
 
-        let stamp = ApiStamp {
-            public_key: self.api_public_key.to_string(),
-            signature: signature_hex,
-            scheme: "SIGNATURE_SCHEME_TK_API_P256".into(),
+        Ok(response_body.activity)
+    }
+
+    /// Retrieves an activity by its ID.
+    ///
+    /// Queries the Turnkey API for the current state of a previously submitted activity.
+    /// This is useful for checking on activities that required approval, or for
+    /// reconciling activities independently of the process that submitted them.
+    ///
+    /// # Arguments
+    ///
+    /// * `activity_id` - The identifier of the activity to look up.
+    ///
+    pub async fn get_activity(&self, activity_id: &str) -> TurnkeyResult<ActivityResponse> {
+        let get_activity_body = GetActivityRequest {
+            organization_id: self.organization_id.clone(),
+            activity_id: activity_id.to_string(),
         };
 
-        let json_stamp = serde_json::to_string(&stamp)?;
-        let encoded_stamp = base64_url::encode(&json_stamp);
+        self.post("/public/v1/query/get_activity", &get_activity_body)
+            .await
+    }
+
+    /// Retrieves the identity that the client's API key authenticates as.
+    ///
+    /// Returns the organization and user the primary API key belongs to, which is useful
+    /// for checking that the client is configured with the intended credentials.
+    pub async fn whoami(&self) -> TurnkeyResult<WhoAmI> {
+        let whoami_body = WhoAmIRequest {
+            organization_id: self.organization_id.clone(),
+        };
 
-        Ok(encoded_stamp)
+        self.post("/public/v1/query/whoami", &whoami_body).await
     }
 
-    /// Signs a transaction using the specified key information.
+    /// Checks that the client can reach the Turnkey API and authenticate.
     ///
-    /// Asynchronously signs the provided `transaction` using the private key associated with the
-    /// selected `key_selector`. This method serializes the transaction's message, signs it, and
-    /// then inserts the signature into the transaction at the appropriate index based on the
-    /// public key's position in the transaction's account keys. It returns the signed transaction
-    /// along with the signature.
+    /// Performs a stamped `whoami` query. Queries are read-only: they create no activities
+    /// and do not count against signing rate limits, so this is safe to call frequently,
+    /// e.g. from a readiness probe.
     ///
-    /// The method ensures that the specified key for signing is part of the transaction's account
-    /// keys, thereby validating the transaction's integrity and authorization.
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::HttpError` if the API cannot be reached, or
+    /// `TurnkeyError::MethodError` if it rejects the client's credentials.
+    pub async fn check_ready(&self) -> TurnkeyResult<()> {
+        self.whoami().await.map(|_| ())
+    }
+
+    /// Lists the accounts of a wallet.
+    ///
+    /// Returns the ID, derivation path, curve and address of every account in the wallet,
+    /// which lets callers discover the addresses to sign with instead of configuring them
+    /// up front.
     ///
     /// # Arguments
     ///
-    /// * `transaction` - A mutable reference to the transaction to be signed. The transaction
-    ///   is modified in place by adding the signature.
-    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
-    ///   signing the transaction. The variant determines the set of key information (private and
-    ///   public keys) used in the signing process.
+    /// * `wallet_id` - The identifier of the wallet whose accounts are listed.
     ///
-    pub async fn sign_transaction(
-        &self,
-        transaction: &mut Transaction,
-        key_selector: KeySelector,
-    ) -> TurnkeyResult<(Transaction, Signature)> {
-        let key_info = self.select_key(key_selector);
-        let serialized_message = transaction.message_data();
+    pub async fn get_wallet_accounts(&self, wallet_id: &str) -> TurnkeyResult<Vec<WalletAccount>> {
+        let get_wallet_accounts_body = GetWalletAccountsRequest {
+            organization_id: self.organization_id.clone(),
+            wallet_id: wallet_id.to_string(),
+        };
 
-        // get signature
-        let signature_bytes = self
-            .sign_bytes(&serialized_message, key_info.private_key_id.to_string())
+        let response: GetWalletAccountsResponse = self
+            .post(
+                "/public/v1/query/list_wallet_accounts",
+                &get_wallet_accounts_body,
+            )
             .await?;
-        let signature = Signature::try_from(signature_bytes.as_slice())?;
 
-        // add signature to transaction
-        let index = transaction
-            .message
-            .account_keys
-            .iter()
-            .position(|key| key == &key_info.public_key);
+        Ok(response.accounts)
+    }
 
-        match index {
-            Some(i) if i < transaction.signatures.len() => {
-                transaction.signatures[i] = signature;
-                Ok((transaction.clone(), signature))
+    /// Waits for an activity to reach a final state.
+    ///
+    /// Repeatedly queries the activity with `get_activity` using the configured polling
+    /// interval (or `ActivityPolling::default()` if polling is not enabled) until its status
+    /// is no longer pending. Each time the polling timeout elapses, the escalation policy
+    /// decides whether to keep waiting, abort, or reject the activity.
+    ///
+    /// # Arguments
+    ///
+    /// * `activity_id` - The identifier of the activity to wait for.
+    ///
+    /// # Errors
+    ///
+    /// If the escalation policy aborts, the activity's pending status is returned as
+    /// `TurnkeyError::ActivityPending` or `TurnkeyError::ConsensusNeeded`. If it rejects the
+    /// activity, `TurnkeyError::ActivityRejected` is returned after the rejection is submitted.
+    pub async fn wait_for_activity(&self, activity_id: &str) -> TurnkeyResult<ActivityResponse> {
+        let polling = self.activity_polling.unwrap_or_default();
+        let mut deadline = Instant::now() + polling.timeout;
+        let mut timeouts = 0;
+
+        loop {
+            let response = self.get_activity(activity_id).await?;
+            if !PENDING_ACTIVITY_STATUSES.contains(&response.activity.status.as_str()) {
+                return Ok(response);
             }
-            _ => {
-                return Err(TurnkeyError::OtherError(
-                    "Unknown signer or index out of bounds".into(),
-                ))
+
+            if Instant::now() >= deadline {
+                timeouts += 1;
+                match (self.escalation_policy)(&response.activity, timeouts) {
+                    EscalationDecision::KeepPolling => deadline = Instant::now() + polling.timeout,
+                    EscalationDecision::Abort => {
+                        let activity = &response.activity;
+                        return Err(TurnkeyError::from_activity_status(
+                            &activity.id,
+                            &activity.status,
+                        )
+                        .unwrap_or_else(|| TurnkeyError::ActivityPending(activity.id.clone())));
+                    }
+                    EscalationDecision::Reject => {
+                        self.reject_activity(&response.activity).await?;
+                        return Err(TurnkeyError::ActivityRejected(activity_id.to_string()));
+                    }
+                }
             }
+
+            tokio::time::sleep(polling.interval).await;
         }
     }
 
-    /// Asynchronously signs a byte array with the specified private key.
+    /// Rejects a pending activity.
     ///
-    /// This method constructs a request to sign a given payload represented by `bytes` using the
-    /// private key identified by `private_key_id`. It sends this request to the Turnkey API,
-    /// specifying that the payload is in hexadecimal format and that no hash function is applied
-    /// before signing. The method waits for the signing operation to complete and processes the
-    /// response to extract the signature.
-    ///
-    /// The signature process involves creating a digital stamp (`x_stamp`) for the request body,
-    /// sending the request to the Turnkey API's sign raw payload endpoint, and then interpreting
-    /// the response to retrieve the actual signature bytes.
+    /// Submits a `REJECT_ACTIVITY` activity for the given activity's fingerprint so that it
+    /// can no longer be approved.
     ///
     /// # Arguments
     ///
-    /// * `bytes` - The byte array to be signed, represented as a slice of bytes (`&[u8]`).
-    /// * `private_key_id` - A `String` representing the identifier of the private key to use for
-    ///   signing the payload.
+    /// * `activity` - The pending activity to reject.
     ///
-    async fn sign_bytes(&self, bytes: &[u8], private_key_id: String) -> TurnkeyResult<Vec<u8>> {
-        let sign_raw_payload_body = SignRawPayloadRequest {
-            activity_type: "ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2".to_string(),
+    async fn reject_activity(&self, activity: &Activity) -> TurnkeyResult<ActivityResponse> {
+        let fingerprint = activity.fingerprint.clone().ok_or_else(|| {
+            TurnkeyError::OtherError(format!("Activity {} has no fingerprint", activity.id))
+        })?;
+
+        let reject_activity_body = RejectActivityRequest {
+            activity_type: "ACTIVITY_TYPE_REJECT_ACTIVITY".to_string(),
             timestamp_ms: chrono::Utc::now().timestamp_millis().to_string(),
             organization_id: self.organization_id.clone(),
-            parameters: SignRawPayloadIntentV2Parameters {
-                sign_with: private_key_id,
-                payload: bytes_to_hex(bytes)?,
-                encoding: "PAYLOAD_ENCODING_HEXADECIMAL".to_string(),
-                hash_function: "HASH_FUNCTION_NOT_APPLICABLE".to_string(),
-            },
+            parameters: RejectActivityIntentParameters { fingerprint },
+        };
+
+        let response_body: ActivityResponse = self
+            .post("/public/v1/submit/reject_activity", &reject_activity_body)
+            .await?;
+        self.verify_activity_type(&response_body.activity, &reject_activity_body.activity_type)?;
+
+        Ok(response_body)
+    }
+
+    /// Checks that `activity` has the `expected` activity type, if verification is enabled.
+    fn verify_activity_type(&self, activity: &Activity, expected: &str) -> TurnkeyResult<()> {
+        if self.verify_activity_type && activity.activity_type != expected {
+            return Err(TurnkeyError::OtherError(format!(
+                "Activity {} has type {}, expected {}",
+                activity.id, activity.activity_type, expected
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether an activity timestamp is older than the client's maximum stamp age.
+    fn is_stale(&self, timestamp_ms: &str) -> bool {
+        let age_ms = timestamp_ms
+            .parse::<i64>()
+            .map(|timestamp_ms| chrono::Utc::now().timestamp_millis() - timestamp_ms)
+            .unwrap_or(i64::MAX);
+
+        age_ms > self.max_stamp_age.as_millis() as i64
+    }
+
+    /// Returns `body` with a fresh `timestampMs` if its current one is stale.
+    ///
+    /// The field is looked up under the client's `ApiVersion` name for `timestampMs`.
+    /// Returns `None` if the timestamp is still fresh, or if `body` has no such top-level
+    /// field.
+    fn refresh_stale_timestamp(&self, body: &str) -> TurnkeyResult<Option<String>> {
+        let mut value: serde_json::Value = serde_json::from_str(body)?;
+        let field = self.api_version.field_name("timestampMs");
+        let Some(timestamp_ms) = value.get_mut(field) else {
+            return Ok(None);
         };
+        if !timestamp_ms
+            .as_str()
+            .is_some_and(|timestamp_ms| self.is_stale(timestamp_ms))
+        {
+            return Ok(None);
+        }
+
+        *timestamp_ms = chrono::Utc::now().timestamp_millis().to_string().into();
+        Ok(Some(serde_json::to_string(&value)?))
+    }
+
+    /// Acquires a permit from the request limiter, if one is configured.
+    ///
+    /// Returns `None` when the number of concurrent signing requests is unlimited.
+    async fn acquire_request_permit(&self) -> TurnkeyResult<Option<SemaphorePermit<'_>>> {
+        match &self.request_limiter {
+            Some(limiter) => limiter
+                .acquire()
+                .await
+                .map(Some)
+                .map_err(|e| TurnkeyError::OtherError(format!("Request limiter closed: {}", e))),
+            None => Ok(None),
+        }
+    }
+
+    /// Sends a stamped request to the Turnkey API with the client's default retry policy.
+    ///
+    /// Retries resend the original stamp, so that a retried activity submission is never
+    /// mistaken for a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API path to post to, e.g. `/public/v1/query/get_activity`.
+    /// * `request` - The request body to serialize and send.
+    ///
+    async fn post<B, T>(&self, path: &str, request: &B) -> TurnkeyResult<T>
+    where
+        B: Serialize,
+        T: for<'de> Deserialize<'de> + 'static,
+    {
+        self.post_with_retry_policy(path, request, &self.retry_policy, false)
+            .await
+    }
+
+    /// Sends a stamped request to the Turnkey API.
+    ///
+    /// Serializes `request` as the JSON body, creates a digital stamp for it, posts it to
+    /// the given API `path`, and deserializes the response via `process_response`. If the
+    /// API rejects the stamp with an authentication failure and backup API keys are
+    /// registered, the request is re-stamped and sent with the next key. Transient failures
+    /// are retried according to `retry_policy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The API path to post to, e.g. `/public/v1/query/get_activity`.
+    /// * `request` - The request body to serialize and send.
+    /// * `retry_policy` - The `RetryPolicy` to send the request with.
+    /// * `refresh_timestamp` - Whether a retry whose `timestampMs` has grown older than the
+    ///   client's maximum stamp age is sent with a fresh timestamp and stamp. Otherwise
+    ///   retries resend the original stamp.
+    ///
+    async fn post_with_retry_policy<B, T>(
+        &self,
+        path: &str,
+        request: &B,
+        retry_policy: &RetryPolicy,
+        refresh_timestamp: bool,
+    ) -> TurnkeyResult<T>
+    where
+        B: Serialize,
+        T: for<'de> Deserialize<'de> + 'static,
+    {
+        let body = self.api_version.to_json(request)?;
+        let url = format!("{}{}", TURNKEY_API_URL, path);
+
+        if let Some(stamper) = &self.stamper {
+            let stamp = stamper.stamp(&body)?;
+            tracing::debug!(
+                url = %url,
+                stamp_header = stamp.header_name,
+                stamp = %self.stamp_logging.redact(&stamp.header_value),
+                "Sending Turnkey API request"
+            );
+
+            let restamp = refresh_timestamp.then_some(stamper.as_ref());
+            let response = self
+                .send(
+                    &url,
+                    self.stamped_headers(&stamp)?,
+                    &body,
+                    retry_policy,
+                    restamp,
+                )
+                .await;
+            return self.process_response::<T>(response);
+        }
+
+        let mut api_keys = self.api_keys.iter().peekable();
+        while let Some(api_key) = api_keys.next() {
+            let stamp = api_key.stamp(&body)?;
+            tracing::debug!(
+                url = %url,
+                api_public_key = %api_key.public_key,
+                x_stamp = %self.stamp_logging.redact(&stamp.header_value),
+                "Sending Turnkey API request"
+            );
+
+            let restamp = refresh_timestamp.then_some(api_key as &dyn Stamper);
+            let response = self
+                .send(
+                    &url,
+                    self.stamped_headers(&stamp)?,
+                    &body,
+                    retry_policy,
+                    restamp,
+                )
+                .await;
+
+            // fail over to the next API key if this one was rejected
+            let is_auth_failure = match &response {
+                Ok(res) => {
+                    res.status == StatusCode::UNAUTHORIZED || res.status == StatusCode::FORBIDDEN
+                }
+                Err(_) => false,
+            };
+            if is_auth_failure && api_keys.peek().is_some() {
+                continue;
+            }
+
+            return self.process_response::<T>(response);
+        }
+
+        Err(TurnkeyError::OtherError("No API keys configured".into()))
+    }
+
+    /// Sends a request through the transport, retrying transient failures.
+    ///
+    /// Each attempt is bounded by the policy's timeout, if any. The response of the last
+    /// attempt is returned once it succeeds, fails permanently, or retries are exhausted.
+    ///
+    /// If `restamp` is given, a retry whose `timestampMs` has gone stale during backoff is
+    /// sent with a fresh timestamp, stamped by `restamp`.
+    async fn send(
+        &self,
+        url: &str,
+        mut headers: HeaderMap,
+        body: &str,
+        retry_policy: &RetryPolicy,
+        restamp: Option<&dyn Stamper>,
+    ) -> TurnkeyResult<TransportResponse> {
+        let mut body = body.to_string();
+        let mut attempt = 0;
+        loop {
+            let request = self.transport.post(url, headers.clone(), body.clone());
+            let response = match retry_policy.timeout {
+                Some(timeout) => {
+                    tokio::time::timeout(timeout, request)
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(TurnkeyError::OtherError(format!(
+                                "Request to {} timed out after {:?}",
+                                url, timeout
+                            )))
+                        })
+                }
+                None => request.await,
+            };
 
-        let body = serde_json::to_string(&sign_raw_payload_body)?;
-        let x_stamp = self.stamp(&body)?;
+            if attempt >= retry_policy.max_retries || !is_retryable(&response) {
+                return response;
+            }
 
-        let response = self
-            .client
-            .post("https://api.turnkey.com/public/v1/submit/sign_raw_payload")
-            .header("Content-Type", "application/json")
-            .header("X-Stamp", &x_stamp)
-            .body(body)
-            .send()
-            .await;
+            tokio::time::sleep(retry_policy.delay(attempt)).await;
+            attempt += 1;
 
-        let response_body = self.process_response::<ActivityResponse>(response).await?;
+            // re-stamp with a fresh timestamp if the request went stale during backoff
+            if let Some(stamper) = restamp {
+                if let Some(fresh_body) = self.refresh_stale_timestamp(&body)? {
+                    let stamp = stamper.stamp(&fresh_body)?;
+                    tracing::debug!(
+                        url = %url,
+                        attempt,
+                        stamp = %self.stamp_logging.redact(&stamp.header_value),
+                        "Re-stamping stale Turnkey API request before retry"
+                    );
+                    headers = self.stamped_headers(&stamp)?;
+                    body = fresh_body;
+                }
+            }
+        }
+    }
 
-        if let Some(result) = response_body.activity.result {
-            if let Some(result) = result.sign_raw_payload_result {
-                let concatenated_hex = format!("{}{}", result.r, result.s);
-                let signature_bytes = hex_to_bytes(&concatenated_hex)?;
+    /// Checks that a response can be deserialized as JSON.
+    ///
+    /// Responses without a `Content-Type` header are accepted, since their body is still
+    /// checked when it is deserialized.
+    fn check_response(&self, res: &TransportResponse) -> TurnkeyResult<()> {
+        let content_type = res
+            .headers
+            .get(CONTENT_TYPE)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+        let unexpected = |reason: String| TurnkeyError::UnexpectedResponse {
+            reason,
+            content_type: content_type.clone(),
+            body: body_snippet(&res.body),
+            status: Some(res.status.as_u16()),
+            request_id: request_id(res),
+        };
 
-                return Ok(signature_bytes);
+        if let Some(max_response_size) = self.max_response_size {
+            if res.body.len() > max_response_size {
+                return Err(unexpected(format!(
+                    "body of {} bytes exceeds the maximum response size of {} bytes",
+                    res.body.len(),
+                    max_response_size
+                )));
             }
         }
 
-        return Err(TurnkeyError::OtherError(
-            "Missing SIGN_RAW_PAYLOAD result".into(),
-        ));
+        let is_json = content_type.as_deref().is_none_or(|content_type| {
+            content_type
+                .split(';')
+                .next()
+                .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+        });
+        if !is_json {
+            return Err(unexpected("response is not JSON".into()));
+        }
+
+        Ok(())
     }
 
     /// Processes an HTTP response, handling success and error
     /// scenarios.
     ///
-    /// This method takes a `Result` from an HTTP request and attempts
-    /// to deserialize the response into the specified generic type
-    /// `T` on success, or into a `TurnkeyError` on failure.
+    /// This method takes a `Result` from the transport and attempts
+    /// to deserialize the response body into the specified generic
+    /// type `T` on success, or into a `TurnkeyError` on failure.
     ///
     /// # Arguments
     ///
-    /// * `response` - A `Result` containing either a
-    ///   `reqwest::Response` or a `reqwest::Error`.
+    /// * `response` - A `TurnkeyResult` containing either the
+    ///   `TransportResponse` or the error raised by the transport.
     ///
     /// # Type Parameters
     ///
@@ -248,36 +2665,114 @@ impl Turnkey {
     ///
     /// # Errors
     ///
-    /// Returns `TurnkeyError::HttpError` if there is a problem with the
-    /// HTTP request itself, or `TurnkeyError::MethodError` if the
-    /// API returns an error response.
-    async fn process_response<T>(
-        &self,
-        response: Result<reqwest::Response, reqwest::Error>,
-    ) -> TurnkeyResult<T>
+    /// Returns the transport's error (usually `TurnkeyError::HttpError`)
+    /// if there is a problem with the HTTP request itself, or
+    /// `TurnkeyError::MethodError` if the API returns an error response,
+    /// or `TurnkeyError::Validation` if that response names invalid fields.
+    /// Responses with a non-JSON `Content-Type`, or that exceed the
+    /// maximum response size, are rejected with
+    /// `TurnkeyError::UnexpectedResponse`. If the body does not have the
+    /// expected shape, `TurnkeyError::DeserializationError` is returned
+    /// with the raw response body. All of these carry the response's HTTP
+    /// status and `x-request-id` header.
+    fn process_response<T>(&self, response: TurnkeyResult<TransportResponse>) -> TurnkeyResult<T>
     where
         T: for<'de> Deserialize<'de> + 'static,
     {
-        match response {
-            Ok(res) => {
-                if res.status().is_success() {
-                    // On success, deserialize the response into the
-                    // expected type T
-                    res.json::<T>().await.map_err(TurnkeyError::HttpError)
-                } else {
-                    // On failure, attempt to deserialize into the error
-                    // response type
-                    let error_res = res.json::<TurnkeyResponseError>().await;
-                    error_res
-                        .map_err(TurnkeyError::HttpError)
-                        .and_then(|error| Err(TurnkeyError::MethodError(error)))
+        let res = response?;
+        self.check_response(&res)?;
+
+        let status = Some(res.status.as_u16());
+        let request_id = request_id(&res);
+        if res.status.is_success() {
+            // On success, deserialize the response into the
+            // expected type T
+            serde_json::from_str::<T>(&res.body).map_err(|source| {
+                TurnkeyError::DeserializationError {
+                    body: res.body,
+                    source,
+                    status,
+                    request_id,
                 }
-            }
-            Err(e) => {
-                // On a reqwest error, convert it into a
-                // TurnkeyError::HttpError
-                Err(TurnkeyError::HttpError(e))
+            })
+        } else {
+            // On failure, attempt to deserialize into the error
+            // response type
+            match serde_json::from_str::<TurnkeyResponseError>(&res.body) {
+                Ok(mut error) => {
+                    error.status = status;
+                    error.request_id = request_id;
+                    Err(TurnkeyError::from_response_error(error))
+                }
+                Err(source) => Err(TurnkeyError::DeserializationError {
+                    body: res.body,
+                    source,
+                    status,
+                    request_id,
+                }),
             }
         }
     }
+
+    /// Builds the headers of a JSON request authenticated with `stamp`, along with the
+    /// client's static headers.
+    fn stamped_headers(&self, stamp: &Stamp) -> TurnkeyResult<HeaderMap> {
+        let mut headers = self.headers.clone();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            stamp.header_name,
+            HeaderValue::from_str(&stamp.header_value)
+                .map_err(|e| TurnkeyError::OtherError(format!("Invalid stamp header: {}", e)))?,
+        );
+
+        Ok(headers)
+    }
+}
+
+/// Returns the value of a response's `x-request-id` header, if any.
+fn request_id(res: &TransportResponse) -> Option<String> {
+    res.headers
+        .get(REQUEST_ID_HEADER)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+}
+
+/// Returns the first `RESPONSE_SNIPPET_LEN` characters of a response body.
+fn body_snippet(body: &str) -> String {
+    match body.char_indices().nth(RESPONSE_SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+/// Reads a required environment variable, naming it in the error if it is missing.
+fn required_env_var(name: &str) -> TurnkeyResult<String> {
+    env::var(name).map_err(|e| match e {
+        env::VarError::NotPresent => TurnkeyError::MissingConfig(format!("missing {}", name)),
+        env::VarError::NotUnicode(_) => {
+            TurnkeyError::MissingConfig(format!("{} is not valid unicode", name))
+        }
+    })
+}
+
+/// Checks that an ed25519 signature is 64 bytes long and valid for `message` under `public_key`.
+fn verify_ed25519_signature(
+    signature_bytes: &[u8],
+    public_key: &Pubkey,
+    message: &[u8],
+) -> TurnkeyResult<()> {
+    let signature = Signature::try_from(signature_bytes).map_err(|_| {
+        TurnkeyError::SignatureError(format!(
+            "Expected a 64-byte ed25519 signature, got {} bytes",
+            signature_bytes.len()
+        ))
+    })?;
+
+    if !signature.verify(public_key.as_ref(), message) {
+        return Err(TurnkeyError::SignatureError(format!(
+            "Signature does not verify against {}",
+            public_key
+        )));
+    }
+
+    Ok(())
 }
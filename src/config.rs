@@ -0,0 +1,71 @@
+use {serde::Deserialize, std::collections::HashMap};
+
+/// API credentials stored on disk, as loaded by `Turnkey::from_key_file`.
+///
+/// Key files are JSON by default, or TOML when the file has a `.toml` extension.
+/// Fields may be written in either `snake_case` or `camelCase`:
+///
+/// ```json
+/// {
+///     "organizationId": "...",
+///     "apiPublicKey": "...",
+///     "apiPrivateKey": "...",
+///     "exampleKey": { "privateKeyId": "...", "publicKey": "...", "curve": "ed25519" }
+/// }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyFile {
+    #[serde(alias = "organizationId")]
+    pub organization_id: String,
+    #[serde(alias = "apiPublicKey")]
+    pub api_public_key: String,
+    #[serde(alias = "apiPrivateKey")]
+    pub api_private_key: String,
+    #[serde(default, alias = "exampleKey")]
+    pub example_key: Option<KeyFileKey>,
+}
+
+/// A signing key entry in a `KeyFile`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyFileKey {
+    #[serde(alias = "privateKeyId")]
+    pub private_key_id: String,
+    #[serde(alias = "publicKey")]
+    pub public_key: String,
+    /// The key's curve, either `p256` or `ed25519`. Defaults to `p256` when omitted.
+    #[serde(default)]
+    pub curve: Option<String>,
+}
+
+/// A decrypted bundle of every Turnkey secret a client needs, as loaded by
+/// `Turnkey::from_secret_bundle`.
+///
+/// Unlike a `KeyFile`, a bundle may carry several API key pairs, the first of which is the
+/// primary key and the rest backups, and registers signing keys by the label of their
+/// `KeySelector`:
+///
+/// ```json
+/// {
+///     "organizationId": "...",
+///     "apiKeys": [{ "publicKey": "...", "privateKey": "..." }],
+///     "keys": { "example": { "privateKeyId": "...", "publicKey": "..." } }
+/// }
+/// ```
+#[derive(Deserialize, Debug, Clone)]
+pub struct SecretBundle {
+    #[serde(alias = "organizationId")]
+    pub organization_id: String,
+    #[serde(alias = "apiKeys")]
+    pub api_keys: Vec<SecretBundleApiKey>,
+    #[serde(default)]
+    pub keys: HashMap<String, KeyFileKey>,
+}
+
+/// An API key pair in a `SecretBundle`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SecretBundleApiKey {
+    #[serde(alias = "publicKey")]
+    pub public_key: String,
+    #[serde(alias = "privateKey")]
+    pub private_key: String,
+}
@@ -7,6 +7,9 @@ use {
     std::{array::TryFromSliceError, env::VarError, error::Error, fmt},
 };
 
+#[cfg(feature = "solana-rpc")]
+use solana_client::client_error::ClientError;
+
 /// A specialized `Result` type for `Turnkey` operations.
 ///
 /// This type is used throughout the `Turnkey` API for methods that can
@@ -24,9 +27,19 @@ pub enum TurnkeyError {
     ///
     /// This variant is used when the `Turnkey` API returns an error
     /// response.
-    #[allow(private_interfaces)]
     MethodError(TurnkeyResponseError),
 
+    /// Represents a request that the `Turnkey` API rejected for invalid fields.
+    ///
+    /// This variant is used instead of `MethodError` when the error response
+    /// names the offending fields, so that callers can react to each one.
+    /// `status` and `request_id` are as in `TurnkeyResponseError`.
+    Validation {
+        violations: Vec<FieldViolation>,
+        status: Option<u16>,
+        request_id: Option<String>,
+    },
+
     /// Represents an HTTP request error.
     ///
     /// This variant is used for errors encountered during the HTTP
@@ -44,25 +57,159 @@ pub enum TurnkeyError {
     /// The contained `String` provides a human-readable description of the error,
     /// which can be useful for logging, debugging, or displaying an error message
     OtherError(String),
+
+    /// Represents a required configuration value that is missing or unreadable.
+    ///
+    /// The contained `String` names the missing value, e.g. the environment
+    /// variable that `Turnkey::from_env` could not read.
+    MissingConfig(String),
+
+    /// Represents a failure to encode or decode hexadecimal data.
+    ///
+    /// This variant is used when an API private key or a signature returned by
+    /// the `Turnkey` API is not valid hex.
+    HexError(String),
+
+    /// Represents a failure to produce or assemble a signature.
+    ///
+    /// This variant is used for ECDSA errors while stamping requests and for
+    /// signatures returned by the `Turnkey` API that cannot be converted into a
+    /// Solana `Signature`.
+    SignatureError(String),
+
+    /// Represents a failure to serialize a request or deserialize a response.
+    SerializationError(SerdeJsonError),
+
+    /// Represents an API response whose body does not have the expected shape.
+    ///
+    /// `body` holds the raw response body, which shows how the response differs
+    /// from the expected one, and `source` the underlying JSON error. `status`
+    /// and `request_id` are as in `TurnkeyResponseError`.
+    DeserializationError {
+        body: String,
+        source: SerdeJsonError,
+        status: Option<u16>,
+        request_id: Option<String>,
+    },
+
+    /// Represents a payload that is larger than the client's maximum payload size.
+    ///
+    /// The payload is rejected before it is sent; see `Turnkey::with_max_payload_size`.
+    PayloadTooLarge { size: usize, max_size: usize },
+
+    /// Represents an API response that was rejected before being deserialized.
+    ///
+    /// This variant is used when a response is not JSON, e.g. an HTML error page
+    /// from a proxy, or is larger than the client's maximum response size. `reason`
+    /// describes the failed check, `content_type` holds the response's
+    /// `Content-Type` header, if any, and `body` the start of the response body.
+    /// `status` and `request_id` are as in `TurnkeyResponseError`.
+    UnexpectedResponse {
+        reason: String,
+        content_type: Option<String>,
+        body: String,
+        status: Option<u16>,
+        request_id: Option<String>,
+    },
+
+    /// Represents an activity that has been submitted but not processed yet.
+    ///
+    /// The contained `String` is the activity ID, which can be used to look the
+    /// activity up later with `Turnkey::get_activity`.
+    ActivityPending(String),
+
+    /// Represents an activity that is awaiting approvals required by a policy.
+    ///
+    /// This is not a failure: the activity completes once enough approvers have
+    /// acted on it. The contained `String` is the activity ID, which can be used to
+    /// find the activity in the Turnkey dashboard.
+    ConsensusNeeded(String),
+
+    /// Represents an activity that was rejected by an approver.
+    ///
+    /// The contained `String` is the activity ID.
+    ActivityRejected(String),
+
+    /// Represents an activity that Turnkey failed to process.
+    ///
+    /// The contained `String` is the activity ID.
+    ActivityFailed(String),
 }
 
+impl TurnkeyError {
+    /// Wraps an error returned by the `bytes` hex helpers.
+    pub(crate) fn hex(error: Box<dyn Error>) -> Self {
+        TurnkeyError::HexError(error.to_string())
+    }
+
+    /// Maps an error response of the Turnkey API to its corresponding `TurnkeyError`.
+    ///
+    /// Responses that carry field violations become `TurnkeyError::Validation`, and all
+    /// others `TurnkeyError::MethodError`.
+    pub(crate) fn from_response_error(error: TurnkeyResponseError) -> Self {
+        let violations = error
+            .details
+            .iter()
+            .flat_map(|detail| detail.field_violations.iter().cloned())
+            .collect::<Vec<_>>();
+
+        if violations.is_empty() {
+            TurnkeyError::MethodError(error)
+        } else {
+            TurnkeyError::Validation {
+                violations,
+                status: error.status,
+                request_id: error.request_id,
+            }
+        }
+    }
+
+    /// Maps a non-completed activity status to its corresponding `TurnkeyError`.
+    ///
+    /// Returns `None` for statuses that do not represent an error, such as
+    /// `ACTIVITY_STATUS_COMPLETED`.
+    pub(crate) fn from_activity_status(activity_id: &str, status: &str) -> Option<Self> {
+        let activity_id = activity_id.to_string();
+        match status {
+            "ACTIVITY_STATUS_CREATED" | "ACTIVITY_STATUS_PENDING" => {
+                Some(TurnkeyError::ActivityPending(activity_id))
+            }
+            "ACTIVITY_STATUS_CONSENSUS_NEEDED" => Some(TurnkeyError::ConsensusNeeded(activity_id)),
+            "ACTIVITY_STATUS_REJECTED" => Some(TurnkeyError::ActivityRejected(activity_id)),
+            "ACTIVITY_STATUS_FAILED" => Some(TurnkeyError::ActivityFailed(activity_id)),
+            _ => None,
+        }
+    }
+}
+
+/// The error body returned by the Turnkey API when a request fails.
+///
+/// `status` and `request_id` are not part of the body: they hold the HTTP status code
+/// and the `x-request-id` header of the response, which Turnkey support uses to trace
+/// the failed request.
 #[derive(Deserialize, Debug, Clone)]
-pub(crate) struct TurnkeyResponseError {
+pub struct TurnkeyResponseError {
     pub code: u32,
     pub message: String,
     pub details: Vec<ErrorDetail>,
+    #[serde(skip)]
+    pub status: Option<u16>,
+    #[serde(skip)]
+    pub request_id: Option<String>,
 }
 
+/// Additional detail attached to a `TurnkeyResponseError`.
 #[derive(Deserialize, Debug, Clone)]
-pub(crate) struct ErrorDetail {
+pub struct ErrorDetail {
     #[serde(rename = "@type")]
     pub type_field: String,
-    #[serde(rename = "fieldViolations")]
+    #[serde(rename = "fieldViolations", default)]
     pub field_violations: Vec<FieldViolation>,
 }
 
+/// Describes a single request field that the Turnkey API rejected.
 #[derive(Deserialize, Debug, Clone)]
-pub(crate) struct FieldViolation {
+pub struct FieldViolation {
     pub field: String,
     pub description: String,
 }
@@ -81,25 +228,38 @@ impl From<VarError> for TurnkeyError {
 
 impl From<EcdsaError> for TurnkeyError {
     fn from(error: EcdsaError) -> Self {
-        TurnkeyError::OtherError(format!("ECDSA error: {}", error))
+        TurnkeyError::SignatureError(format!("ECDSA error: {}", error))
     }
 }
 
 impl From<SerdeJsonError> for TurnkeyError {
     fn from(error: SerdeJsonError) -> Self {
-        TurnkeyError::OtherError(format!("Serde JSON error: {}", error))
+        TurnkeyError::SerializationError(error)
     }
 }
 
 impl From<ParsePubkeyError> for TurnkeyError {
     fn from(error: ParsePubkeyError) -> Self {
-        TurnkeyError::OtherError(format!("Serde JSON error: {}", error))
+        TurnkeyError::OtherError(format!("Pubkey parse error: {}", error))
+    }
+}
+
+impl From<ReqwestError> for TurnkeyError {
+    fn from(error: ReqwestError) -> Self {
+        TurnkeyError::HttpError(error)
     }
 }
 
 impl From<TryFromSliceError> for TurnkeyError {
     fn from(error: TryFromSliceError) -> Self {
-        TurnkeyError::OtherError(format!("Signature conversion error: {}", error))
+        TurnkeyError::SignatureError(format!("Signature conversion error: {}", error))
+    }
+}
+
+#[cfg(feature = "solana-rpc")]
+impl From<ClientError> for TurnkeyError {
+    fn from(error: ClientError) -> Self {
+        TurnkeyError::OtherError(format!("RPC error: {}", error))
     }
 }
 
@@ -107,15 +267,72 @@ impl fmt::Display for TurnkeyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TurnkeyError::MethodError(e) => write!(f, "{}", e),
+            TurnkeyError::Validation {
+                violations,
+                status,
+                request_id,
+            } => {
+                write!(f, "Validation error:")?;
+                for violation in violations {
+                    write!(f, " {}: {};", violation.field, violation.description)?;
+                }
+                write_response_context(f, *status, request_id.as_deref())
+            }
             TurnkeyError::HttpError(e) => write!(f, "HTTP error: {}", e),
             TurnkeyError::OtherError(e) => write!(f, "Other error: {}", e),
+            TurnkeyError::MissingConfig(e) => write!(f, "Configuration error: {}", e),
+            TurnkeyError::HexError(e) => write!(f, "Hex error: {}", e),
+            TurnkeyError::SignatureError(e) => write!(f, "Signature error: {}", e),
+            TurnkeyError::SerializationError(e) => write!(f, "Serialization error: {}", e),
+            TurnkeyError::DeserializationError {
+                body,
+                source,
+                status,
+                request_id,
+            } => {
+                write!(
+                    f,
+                    "Failed to deserialize response: {} (body: {})",
+                    source, body
+                )?;
+                write_response_context(f, *status, request_id.as_deref())
+            }
+            TurnkeyError::PayloadTooLarge { size, max_size } => write!(
+                f,
+                "Payload of {} bytes exceeds the maximum payload size of {} bytes",
+                size, max_size
+            ),
+            TurnkeyError::UnexpectedResponse {
+                reason,
+                content_type,
+                body,
+                status,
+                request_id,
+            } => {
+                write!(
+                    f,
+                    "Unexpected response: {} (content-type: {}, body: {})",
+                    reason,
+                    content_type.as_deref().unwrap_or("none"),
+                    body
+                )?;
+                write_response_context(f, *status, request_id.as_deref())
+            }
+            TurnkeyError::ActivityPending(id) => write!(f, "Activity {} is still pending", id),
+            TurnkeyError::ConsensusNeeded(id) => {
+                write!(f, "Activity {} is awaiting consensus approvals", id)
+            }
+            TurnkeyError::ActivityRejected(id) => write!(f, "Activity {} was rejected", id),
+            TurnkeyError::ActivityFailed(id) => write!(f, "Activity {} failed", id),
         }
     }
 }
 
 impl fmt::Display for TurnkeyResponseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Error Code: {}, Message: {}", self.code, self.message)?;
+        write!(f, "Error Code: {}, Message: {}", self.code, self.message)?;
+        write_response_context(f, self.status, self.request_id.as_deref())?;
+        writeln!(f)?;
         for detail in &self.details {
             writeln!(f, "Detail: {}", detail.type_field)?;
             for violation in &detail.field_violations {
@@ -129,3 +346,18 @@ impl fmt::Display for TurnkeyResponseError {
         Ok(())
     }
 }
+
+/// Writes the HTTP status and request ID of an error response, if known.
+fn write_response_context(
+    f: &mut fmt::Formatter<'_>,
+    status: Option<u16>,
+    request_id: Option<&str>,
+) -> fmt::Result {
+    if let Some(status) = status {
+        write!(f, " (HTTP status: {})", status)?;
+    }
+    if let Some(request_id) = request_id {
+        write!(f, " (request ID: {})", request_id)?;
+    }
+    Ok(())
+}
@@ -1,7 +1,24 @@
+pub mod audit;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod bytes;
-mod models;
-
 pub mod client;
+pub mod config;
 pub mod errors;
+pub mod logging;
+pub mod models;
+pub mod polling;
+pub mod retry;
+#[cfg(feature = "solana-rpc")]
+pub mod rpc;
+pub mod signature;
+pub mod signer;
+pub mod stamp;
+pub mod transaction;
+pub mod transport;
+pub mod verify;
 
-pub use client::{KeySelector, Turnkey};
+pub use client::{
+    Curve, KeySelector, PublicStatus, SignatureComponents, SignedMessage, SignedTransactionBytes,
+    Turnkey,
+};
@@ -0,0 +1,30 @@
+/// Controls how much of a request's X-Stamp header appears in debug logs.
+///
+/// The stamp holds a signature over the request body, so it is hidden by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StampLogging {
+    /// Omit the stamp entirely.
+    #[default]
+    None,
+    /// Include only the first few characters of the stamp, enough to correlate requests.
+    Prefix,
+    /// Include the full stamp.
+    Full,
+}
+
+/// The number of stamp characters logged with `StampLogging::Prefix`.
+const STAMP_PREFIX_LEN: usize = 8;
+
+impl StampLogging {
+    /// Returns the form of `stamp` that may be written to logs under this setting.
+    pub fn redact(&self, stamp: &str) -> String {
+        match self {
+            StampLogging::None => "<redacted>".to_string(),
+            StampLogging::Prefix => {
+                let prefix: String = stamp.chars().take(STAMP_PREFIX_LEN).collect();
+                format!("{}...", prefix)
+            }
+            StampLogging::Full => stamp.to_string(),
+        }
+    }
+}
@@ -1,4 +1,63 @@
-use serde::{Deserialize, Serialize};
+use {
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Deserializer, Serialize},
+    serde_json::Value,
+    std::collections::HashMap,
+};
+
+/// The naming scheme used for the JSON fields of requests sent to the Turnkey API.
+///
+/// The request models are defined with the field names of the current API version. If a
+/// future API version renames request fields, `Renamed` maps the current names to the new
+/// ones at serialization time so the crate can target it without changing the models.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// The field names of the `v1` public API, as defined by the request models.
+    #[default]
+    V1,
+    /// The `v1` field names, with each key of the map renamed to its value.
+    Renamed(HashMap<String, String>),
+}
+
+impl ApiVersion {
+    /// Serializes a request model to JSON using this version's field names.
+    pub fn to_json<T: Serialize>(&self, request: &T) -> serde_json::Result<String> {
+        match self {
+            ApiVersion::V1 => serde_json::to_string(request),
+            ApiVersion::Renamed(renames) => {
+                let mut value = serde_json::to_value(request)?;
+                rename_fields(&mut value, renames);
+                serde_json::to_string(&value)
+            }
+        }
+    }
+
+    /// Returns the name this version uses for the `v1` request field `name`.
+    pub fn field_name<'a>(&'a self, name: &'a str) -> &'a str {
+        match self {
+            ApiVersion::V1 => name,
+            ApiVersion::Renamed(renames) => renames.get(name).map_or(name, String::as_str),
+        }
+    }
+}
+
+/// Recursively renames the object keys of `value` according to `renames`.
+fn rename_fields(value: &mut Value, renames: &HashMap<String, String>) {
+    match value {
+        Value::Object(map) => {
+            let fields = std::mem::take(map);
+            for (key, mut field) in fields {
+                rename_fields(&mut field, renames);
+                let key = renames.get(&key).cloned().unwrap_or(key);
+                map.insert(key, field);
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| rename_fields(item, renames)),
+        _ => {}
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +78,255 @@ pub struct SignRawPayloadIntentV2Parameters {
     pub hash_function: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActivityRequest {
+    pub organization_id: String,
+    pub activity_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WhoAmIRequest {
+    pub organization_id: String,
+}
+
+/// The identity that authenticated a request, as returned by `Turnkey::whoami`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WhoAmI {
+    pub organization_id: String,
+    pub organization_name: String,
+    pub user_id: String,
+    pub username: String,
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWalletAccountsRequest {
+    pub organization_id: String,
+    pub wallet_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetWalletAccountsResponse {
+    pub accounts: Vec<WalletAccount>,
+}
+
+/// An account derived in a wallet, as returned by `Turnkey::get_wallet_accounts`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAccount {
+    pub wallet_account_id: String,
+    pub wallet_id: String,
+    pub curve: String,
+    pub path_format: String,
+    pub path: String,
+    pub address_format: String,
+    pub address: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectActivityRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: RejectActivityIntentParameters,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectActivityIntentParameters {
+    pub fingerprint: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWalletRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: CreateWalletIntentParameters,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWalletIntentParameters {
+    pub wallet_name: String,
+    pub accounts: Vec<WalletAccountParams>,
+}
+
+/// The derivation parameters of an account in a wallet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletAccountParams {
+    pub curve: String,
+    pub path_format: String,
+    pub path: String,
+    pub address_format: String,
+}
+
+impl WalletAccountParams {
+    /// Returns the parameters of the Solana account at `account_index`, derived with the
+    /// standard `m/44'/501'/{account_index}'/0'` path.
+    pub fn solana(account_index: u32) -> Self {
+        Self {
+            curve: "CURVE_ED25519".to_string(),
+            path_format: "PATH_FORMAT_BIP32".to_string(),
+            path: format!("m/44'/501'/{}'/0'", account_index),
+            address_format: "ADDRESS_FORMAT_SOLANA".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubOrgRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: CreateSubOrgParams,
+}
+
+/// The parameters of a new sub-organization, as passed to `Turnkey::create_sub_organization`.
+///
+/// `root_quorum_threshold` is the number of root users that must approve activities in the
+/// sub-organization. If `wallet` is given, a wallet is created in the sub-organization
+/// along with it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubOrgParams {
+    pub sub_organization_name: String,
+    pub root_users: Vec<RootUserParams>,
+    pub root_quorum_threshold: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet: Option<WalletParams>,
+}
+
+/// A root user of a new sub-organization, with the credentials they authenticate with.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RootUserParams {
+    pub user_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_email: Option<String>,
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyParams>,
+    #[serde(default)]
+    pub authenticators: Vec<AuthenticatorParams>,
+    #[serde(default)]
+    pub oauth_providers: Vec<OauthProviderParams>,
+}
+
+/// An API key of a root user, e.g. with `curve_type` `API_KEY_CURVE_P256`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyParams {
+    pub api_key_name: String,
+    pub public_key: String,
+    pub curve_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_seconds: Option<String>,
+}
+
+/// A passkey of a root user, registered with the attestation of its creation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorParams {
+    pub authenticator_name: String,
+    pub challenge: String,
+    pub attestation: Attestation,
+}
+
+/// The WebAuthn attestation of a newly created passkey.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Attestation {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub attestation_object: String,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// An OIDC provider a root user can log in with.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OauthProviderParams {
+    pub provider_name: String,
+    pub oidc_token: String,
+}
+
+/// A wallet to create along with a sub-organization.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletParams {
+    pub wallet_name: String,
+    pub accounts: Vec<WalletAccountParams>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mnemonic_length: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPrivateKeyRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: ExportPrivateKeyIntentParameters,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPrivateKeyIntentParameters {
+    pub private_key_id: String,
+    pub target_public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitImportPrivateKeyRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: InitImportPrivateKeyIntentParameters,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitImportPrivateKeyIntentParameters {
+    pub user_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPrivateKeyRequest {
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub timestamp_ms: String,
+    pub organization_id: String,
+    pub parameters: ImportPrivateKeyIntentParameters,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPrivateKeyIntentParameters {
+    pub user_id: String,
+    pub private_key_name: String,
+    pub encrypted_bundle: String,
+    pub curve: String,
+    pub address_formats: Vec<String>,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivityResponse {
@@ -31,15 +339,81 @@ pub struct Activity {
     pub id: String,
     pub organization_id: String,
     pub status: String,
+    pub fingerprint: Option<String>,
     pub result: Option<ActivityResult>,
     #[serde(rename = "type")]
     pub activity_type: String,
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+    #[serde(default)]
+    pub updated_at: Option<Timestamp>,
+    #[serde(default)]
+    pub votes: Vec<Vote>,
+}
+
+impl Activity {
+    /// Returns the votes of approvers who approved the activity, in the order they were cast.
+    pub fn approvals(&self) -> impl Iterator<Item = &Vote> {
+        self.votes
+            .iter()
+            .filter(|vote| vote.selection == "VOTE_SELECTION_APPROVED")
+    }
+}
+
+/// A vote cast by an approver on an activity gated by a consensus policy.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+    pub id: String,
+    pub user_id: String,
+    pub activity_id: String,
+    /// Either `VOTE_SELECTION_APPROVED` or `VOTE_SELECTION_REJECTED`.
+    pub selection: String,
+    #[serde(default)]
+    pub created_at: Option<Timestamp>,
+}
+
+/// A point in time as returned by the Turnkey API, e.g. `{"seconds": "1700000000", "nanos": "0"}`.
+///
+/// Both fields are accepted as either JSON strings or numbers.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    #[serde(deserialize_with = "deserialize_integer")]
+    pub seconds: i64,
+    #[serde(default, deserialize_with = "deserialize_integer")]
+    pub nanos: u32,
+}
+
+impl Timestamp {
+    /// Converts the timestamp into a `DateTime<Utc>`, or `None` if it is out of range.
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.seconds, self.nanos)
+    }
+}
+
+/// Deserializes an integer that may be encoded as a JSON string or number.
+fn deserialize_integer<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr + TryFrom<i64>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_i64().and_then(|n| T::try_from(n).ok()),
+        _ => None,
+    }
+    .ok_or_else(|| serde::de::Error::custom("expected an integer or integer string"))
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivityResult {
     pub sign_raw_payload_result: Option<SignRawPayloadResult>,
+    pub create_wallet_result: Option<CreateWalletResult>,
+    pub export_private_key_result: Option<ExportResult>,
+    pub create_sub_organization_result_v7: Option<CreateSubOrgResult>,
+    pub init_import_private_key_result: Option<InitImportResult>,
+    pub import_private_key_result: Option<ImportResult>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -47,6 +421,64 @@ pub struct ActivityResult {
 pub struct SignRawPayloadResult {
     pub r: String,
     pub s: String,
+    #[serde(default)]
+    pub v: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWalletResult {
+    pub wallet_id: String,
+    pub addresses: Vec<String>,
+}
+
+/// A newly created sub-organization, with the wallet created along with it, if any.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubOrgResult {
+    pub sub_organization_id: String,
+    #[serde(default)]
+    pub wallet: Option<CreateWalletResult>,
+    #[serde(default)]
+    pub root_user_ids: Vec<String>,
+}
+
+/// A private key exported from Turnkey.
+///
+/// `export_bundle` is encrypted with HPKE to the target public key the export was requested
+/// for, and must be decrypted with the corresponding private key.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub private_key_id: String,
+    pub export_bundle: String,
+}
+
+/// The target key of a private key import, as returned by `Turnkey::init_import_private_key`.
+///
+/// `import_bundle` holds Turnkey's signed target public key, which the key material must be
+/// encrypted to with HPKE before it is passed to `Turnkey::import_private_key`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InitImportResult {
+    pub import_bundle: String,
+}
+
+/// A private key imported into Turnkey, with the addresses derived from it.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    pub private_key_id: String,
+    #[serde(default)]
+    pub addresses: Vec<PrivateKeyAddress>,
+}
+
+/// An address of a private key, e.g. with `format` `ADDRESS_FORMAT_SOLANA`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateKeyAddress {
+    pub format: String,
+    pub address: String,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -54,5 +486,5 @@ pub struct SignRawPayloadResult {
 pub struct ApiStamp {
     pub public_key: String,
     pub signature: String,
-    pub scheme: &'static str,
+    pub scheme: String,
 }
@@ -0,0 +1,50 @@
+use {
+    crate::models::Activity,
+    std::{sync::Arc, time::Duration},
+};
+
+/// Activity statuses that indicate the activity has not reached a final state yet.
+pub(crate) const PENDING_ACTIVITY_STATUSES: [&str; 3] = [
+    "ACTIVITY_STATUS_CREATED",
+    "ACTIVITY_STATUS_PENDING",
+    "ACTIVITY_STATUS_CONSENSUS_NEEDED",
+];
+
+/// Configures how the `Turnkey` client waits for pending activities to complete.
+///
+/// Activities gated by a consensus policy are not completed immediately. When polling
+/// is enabled, the client re-queries such activities every `interval` until they reach
+/// a final state. Once `timeout` elapses without a final state, the client's
+/// `EscalationPolicy` decides how to proceed.
+#[derive(Debug, Clone, Copy)]
+pub struct ActivityPolling {
+    pub interval: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for ActivityPolling {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The action to take when a pending activity times out waiting for approvals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationDecision {
+    /// Keep polling for another `timeout` period, e.g. after notifying approvers.
+    KeepPolling,
+    /// Stop polling and return an error, leaving the activity pending in Turnkey.
+    Abort,
+    /// Reject the activity in Turnkey and return an error.
+    Reject,
+}
+
+/// A callback invoked each time a pending activity times out.
+///
+/// The callback receives the latest state of the activity and the number of timeouts
+/// that have occurred so far (starting at 1), and returns the `EscalationDecision`
+/// the client should act on.
+pub type EscalationPolicy = Arc<dyn Fn(&Activity, u32) -> EscalationDecision + Send + Sync>;
@@ -0,0 +1,67 @@
+use {
+    crate::{errors::TurnkeyResult, transport::TransportResponse},
+    rand::Rng,
+    reqwest::StatusCode,
+    std::time::Duration,
+};
+
+/// Configures how requests to the Turnkey API are retried and timed out.
+///
+/// A request is retried when the transport fails, when an attempt exceeds `timeout`, or when
+/// the API responds with `429 Too Many Requests` or a server error. Retries are delayed by
+/// `backoff`, doubling after every attempt, and randomized according to `jitter`. The default
+/// policy never retries and has no timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+    pub timeout: Option<Duration>,
+    pub jitter: Jitter,
+}
+
+/// Randomizes retry delays so that clients failing at the same time don't retry in lockstep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Jitter {
+    /// Wait exactly the exponential backoff delay.
+    #[default]
+    None,
+    /// Wait a random delay between zero and the backoff delay.
+    Full,
+    /// Wait half the backoff delay plus a random delay of up to the other half.
+    Equal,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Duration::from_millis(200),
+            timeout: None,
+            jitter: Jitter::None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the retry that follows the given zero-based `attempt`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff.saturating_mul(2u32.saturating_pow(attempt));
+        let random_up_to = |max: Duration| {
+            Duration::from_nanos(rand::thread_rng().gen_range(0..=max.as_nanos() as u64))
+        };
+
+        match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => random_up_to(delay),
+            Jitter::Equal => delay / 2 + random_up_to(delay - delay / 2),
+        }
+    }
+}
+
+/// Returns whether a response is a transient failure that may succeed when retried.
+pub(crate) fn is_retryable(response: &TurnkeyResult<TransportResponse>) -> bool {
+    match response {
+        Ok(res) => res.status == StatusCode::TOO_MANY_REQUESTS || res.status.is_server_error(),
+        Err(_) => true,
+    }
+}
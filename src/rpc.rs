@@ -0,0 +1,190 @@
+use {
+    crate::{
+        client::{KeySelector, Turnkey},
+        errors::{TurnkeyError, TurnkeyResult},
+        transaction::unsigned_signer_indices,
+    },
+    solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        nonce_utils::nonblocking::{data_from_account, get_account_with_commitment},
+        rpc_config::RpcSimulateTransactionConfig,
+        rpc_response::RpcSimulateTransactionResult,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::{uses_durable_nonce, Transaction},
+    },
+    std::time::Duration,
+    tokio::time::Instant,
+};
+
+/// How long to wait between signature status checks while confirming a transaction.
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+impl Turnkey {
+    /// Signs a transaction and submits it to the network in one call.
+    ///
+    /// If none of the transaction's required signatures have been filled in yet, its recent
+    /// blockhash is refreshed from `rpc` before signing so that the transaction is not
+    /// rejected for an expired blockhash. Transactions that use a durable nonce, or that
+    /// already carry signatures, are signed as is.
+    ///
+    /// After submission, the method waits until the transaction reaches the client's
+    /// configured commitment level (see `Turnkey::with_commitment`). A transaction that has
+    /// been processed is waited on even if its blockhash has since expired. Durable nonce
+    /// transactions have no blockhash that can expire, so they are waited on until they
+    /// are confirmed, fail, or their nonce is advanced by another transaction. The wait is
+    /// bounded by the client's confirmation timeout (see `Turnkey::with_confirmation_timeout`).
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed and sent.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    /// * `rpc` - The RPC client used to fetch the blockhash and submit the transaction.
+    ///
+    /// # Errors
+    ///
+    /// RPC failures are returned as `TurnkeyError::OtherError`, as are transactions that fail
+    /// on chain, whose blockhash expires or durable nonce is advanced before they are
+    /// processed, or that are not confirmed within the confirmation timeout.
+    pub async fn sign_and_send(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        rpc: &RpcClient,
+    ) -> TurnkeyResult<Signature> {
+        let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+        let is_unsigned = unsigned_signer_indices(transaction).len() == num_required_signatures;
+        let uses_nonce = uses_durable_nonce(transaction).is_some();
+        if is_unsigned && !uses_nonce {
+            let (blockhash, _) = rpc
+                .get_latest_blockhash_with_commitment(self.commitment())
+                .await?;
+            transaction.message.recent_blockhash = blockhash;
+        }
+
+        let signature = self.sign_transaction(transaction, key_selector).await?;
+        rpc.send_transaction(transaction).await?;
+
+        let nonce_account = uses_durable_nonce(transaction)
+            .and_then(|instruction| instruction.accounts.first())
+            .and_then(|index| transaction.message.account_keys.get(*index as usize))
+            .copied();
+        let deadline = Instant::now() + self.confirmation_timeout();
+        loop {
+            match rpc
+                .get_signature_status_with_commitment(&signature, self.commitment())
+                .await?
+            {
+                Some(Ok(())) => return Ok(signature),
+                Some(Err(e)) => {
+                    return Err(TurnkeyError::OtherError(format!(
+                        "Transaction {} failed: {}",
+                        signature, e
+                    )))
+                }
+                None => {}
+            }
+
+            if let Some(reason) = expiry_reason(transaction, nonce_account, rpc).await? {
+                // the transaction may have landed just before it expired without having
+                // reached the target commitment yet
+                if rpc
+                    .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())
+                    .await?
+                    .is_none()
+                {
+                    return Err(TurnkeyError::OtherError(format!(
+                        "{} before transaction {} was confirmed",
+                        reason, signature
+                    )));
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Err(TurnkeyError::OtherError(format!(
+                    "Transaction {} was not confirmed within {:?}",
+                    signature,
+                    self.confirmation_timeout()
+                )));
+            }
+
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Signs a transaction and simulates it against the network without broadcasting it.
+    ///
+    /// The simulation verifies the transaction's signatures and runs at the client's
+    /// configured commitment level, so a successful result indicates the signed transaction
+    /// would be accepted if sent. Runtime errors are reported in the returned result's `err`
+    /// field rather than as a `TurnkeyError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed and simulated.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    /// * `rpc` - The RPC client used to simulate the transaction.
+    ///
+    pub async fn sign_and_simulate(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+        rpc: &RpcClient,
+    ) -> TurnkeyResult<RpcSimulateTransactionResult> {
+        self.sign_transaction(transaction, key_selector).await?;
+
+        let config = RpcSimulateTransactionConfig {
+            sig_verify: true,
+            commitment: Some(self.commitment()),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        let response = rpc
+            .simulate_transaction_with_config(transaction, config)
+            .await?;
+
+        Ok(response.value)
+    }
+}
+
+/// Returns why `transaction` can no longer land, if it can't.
+///
+/// A regular transaction expires with its recent blockhash. A durable nonce transaction,
+/// whose recent blockhash holds the nonce value, expires once the nonce stored in
+/// `nonce_account` has been advanced past it.
+async fn expiry_reason(
+    transaction: &Transaction,
+    nonce_account: Option<Pubkey>,
+    rpc: &RpcClient,
+) -> TurnkeyResult<Option<&'static str>> {
+    let recent_blockhash = transaction.message.recent_blockhash;
+    match nonce_account {
+        Some(nonce_account) => {
+            let account =
+                get_account_with_commitment(rpc, &nonce_account, CommitmentConfig::processed())
+                    .await
+                    .map_err(|e| {
+                        TurnkeyError::OtherError(format!(
+                            "Failed to read nonce account {}: {}",
+                            nonce_account, e
+                        ))
+                    })?;
+            let nonce_data = data_from_account(&account).map_err(|e| {
+                TurnkeyError::OtherError(format!("Invalid nonce account {}: {}", nonce_account, e))
+            })?;
+
+            Ok((nonce_data.blockhash() != recent_blockhash).then_some("Durable nonce advanced"))
+        }
+        None => {
+            let is_valid = rpc
+                .is_blockhash_valid(&recent_blockhash, CommitmentConfig::processed())
+                .await?;
+
+            Ok((!is_valid).then_some("Blockhash expired"))
+        }
+    }
+}
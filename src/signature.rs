@@ -0,0 +1,55 @@
+use crate::{
+    bytes::hex_to_bytes,
+    errors::{TurnkeyError, TurnkeyResult},
+};
+
+/// Length of a hex-encoded 32-byte signature component.
+const COMPONENT_HEX_LEN: usize = 64;
+
+/// Assembles the signature bytes from the `r` and `s` values of a `SIGN_RAW_PAYLOAD` result.
+///
+/// The signature is the concatenation `r || s` of the hex-decoded values, which is 64 bytes
+/// for both P256 and ed25519 keys. Turnkey occasionally returns `r` or `s` without leading
+/// zeros, so each value is left-padded to 32 bytes before the two are concatenated.
+///
+/// # Arguments
+///
+/// * `r` - The hex-encoded `r` value returned by Turnkey.
+/// * `s` - The hex-encoded `s` value returned by Turnkey.
+///
+/// # Errors
+///
+/// Returns `TurnkeyError::HexError` if `r` or `s` is not valid hex, or
+/// `TurnkeyError::SignatureError` if either is longer than 32 bytes.
+///
+/// # Examples
+///
+/// ```
+/// use turnkey::signature::assemble_signature;
+///
+/// let signature = assemble_signature(&"01".repeat(32), &"02".repeat(32)).unwrap();
+/// assert_eq!(signature.len(), 64);
+/// assert_eq!(signature[..32], [1; 32]);
+/// assert_eq!(signature[32..], [2; 32]);
+///
+/// // leading zeros of `r` and `s` may be omitted
+/// let signature = assemble_signature(&"01".repeat(31), &"02".repeat(32)).unwrap();
+/// assert_eq!(signature.len(), 64);
+/// assert_eq!(signature[0], 0);
+/// ```
+pub fn assemble_signature(r: &str, s: &str) -> TurnkeyResult<Vec<u8>> {
+    let concatenated_hex = format!("{}{}", pad_component(r)?, pad_component(s)?);
+    hex_to_bytes(&concatenated_hex).map_err(TurnkeyError::hex)
+}
+
+/// Left-pads a hex-encoded signature component with zeros to 32 bytes.
+fn pad_component(component: &str) -> TurnkeyResult<String> {
+    if component.len() > COMPONENT_HEX_LEN {
+        return Err(TurnkeyError::SignatureError(format!(
+            "Signature component {} is longer than 32 bytes",
+            component
+        )));
+    }
+
+    Ok(format!("{:0>width$}", component, width = COMPONENT_HEX_LEN))
+}
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        client::{KeySelector, Turnkey},
+        errors::TurnkeyResult,
+    },
+    async_trait::async_trait,
+    solana_sdk::{signature::Signature, transaction::Transaction},
+};
+
+/// Signs Solana transactions with a selected key.
+///
+/// `Turnkey` implements this trait by delegating to `Turnkey::sign_transaction`. Code that
+/// depends on an `Arc<dyn TransactionSigner>` rather than on `Turnkey` directly can be unit
+/// tested with a mock signer in place of a real client.
+///
+/// # Examples
+///
+/// ```no_run
+/// use {
+///     std::sync::Arc,
+///     turnkey::{signer::TransactionSigner, Turnkey},
+/// };
+///
+/// let signer: Arc<dyn TransactionSigner> = Arc::new(Turnkey::new().unwrap());
+/// ```
+#[async_trait]
+pub trait TransactionSigner: Send + Sync {
+    /// Signs `transaction` in place with the selected key and returns the signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - A mutable reference to the transaction to be signed.
+    /// * `key_selector` - A `KeySelector` variant that specifies which private key to use for
+    ///   signing the transaction.
+    ///
+    async fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature>;
+}
+
+#[async_trait]
+impl TransactionSigner for Turnkey {
+    async fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        Turnkey::sign_transaction(self, transaction, key_selector).await
+    }
+}
@@ -0,0 +1,191 @@
+use {
+    crate::{
+        bytes::{bytes_to_hex, hex_to_bytes},
+        errors::{TurnkeyError, TurnkeyResult},
+        models::ApiStamp,
+    },
+    base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine, GeneralPurpose},
+    p256::ecdsa::{signature::Signer, SigningKey},
+    serde::Serialize,
+    sha2::{Digest, Sha256},
+    std::sync::Arc,
+    zeroize::Zeroize,
+};
+
+/// The stamp of a request: the header that authenticates it and that header's value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stamp {
+    pub header_name: &'static str,
+    pub header_value: String,
+}
+
+/// Produces the stamp that authorizes a request to the Turnkey API.
+///
+/// Stamps are computed over the exact JSON body that is sent. `ApiKeyStamper` signs
+/// requests with an API key, which is what the client does by default, and
+/// `WebauthnStamper` authorizes them with a passkey assertion. Use `Turnkey::with_stamper`
+/// to stamp requests with a custom implementation.
+pub trait Stamper: Send + Sync {
+    /// Stamps the serialized request `body`.
+    fn stamp(&self, body: &str) -> TurnkeyResult<Stamp>;
+}
+
+/// Stamps requests with a P256 API key in the `X-Stamp` header.
+///
+/// The private key is kept only as the decoded `SigningKey`, so it is parsed once when the
+/// stamper is constructed rather than on every stamp.
+#[derive(Clone)]
+pub struct ApiKeyStamper {
+    pub(crate) public_key: String,
+    signing_key: SigningKey,
+    pub(crate) scheme: String,
+    pub(crate) engine: GeneralPurpose,
+}
+
+impl ApiKeyStamper {
+    /// Creates an API key stamper, checking that the private key is usable for stamping.
+    ///
+    /// Misconfigured keys are reported here, when the client is constructed, rather than as
+    /// an ECDSA error on the first request. The hex-encoded private key is zeroized once it
+    /// has been decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - The public half of the API key.
+    /// * `private_key` - The hex-encoded private half of the API key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnkeyError::OtherError` if `private_key` is not a hex-encoded 32-byte
+    /// P256 private key.
+    pub fn new(
+        public_key: impl Into<String>,
+        private_key: impl Into<String>,
+    ) -> TurnkeyResult<Self> {
+        let mut private_key = private_key.into();
+        let decoded = hex_to_bytes(&private_key);
+        private_key.zeroize();
+        let mut private_key_bytes = decoded.map_err(|e| {
+            TurnkeyError::OtherError(format!("API private key is not valid hex: {}", e))
+        })?;
+
+        let signing_key = if private_key_bytes.len() != 32 {
+            Err(TurnkeyError::OtherError(format!(
+                "API private key must be 32 bytes, got {} bytes",
+                private_key_bytes.len()
+            )))
+        } else {
+            SigningKey::from_bytes(&private_key_bytes).map_err(|_| {
+                TurnkeyError::OtherError("API private key is not a valid P256 private key".into())
+            })
+        };
+        private_key_bytes.zeroize();
+
+        Ok(Self {
+            public_key: public_key.into(),
+            signing_key: signing_key?,
+            scheme: "SIGNATURE_SCHEME_TK_API_P256".to_string(),
+            engine: URL_SAFE_NO_PAD,
+        })
+    }
+
+    /// Sets the signature scheme reported in the stamp.
+    ///
+    /// Defaults to `SIGNATURE_SCHEME_TK_API_P256`.
+    pub fn with_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = scheme.into();
+        self
+    }
+
+    /// Sets the base64 engine used to encode the stamp.
+    ///
+    /// Defaults to `base64::engine::general_purpose::URL_SAFE_NO_PAD`.
+    pub fn with_base64_engine(mut self, engine: GeneralPurpose) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Returns the public half of the API key.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+}
+
+impl Stamper for ApiKeyStamper {
+    /// Signs `body` with the API key and returns the base64-encoded `X-Stamp` JSON, which
+    /// holds the DER-encoded signature, the public API key and the signature scheme.
+    fn stamp(&self, body: &str) -> TurnkeyResult<Stamp> {
+        let signature: p256::ecdsa::Signature = self.signing_key.sign(body.as_bytes());
+        let signature_der = signature.to_der().to_bytes();
+        let signature_hex = bytes_to_hex(&signature_der).map_err(TurnkeyError::hex)?;
+
+        let stamp = ApiStamp {
+            public_key: self.public_key.clone(),
+            signature: signature_hex,
+            scheme: self.scheme.clone(),
+        };
+
+        let json_stamp = serde_json::to_string(&stamp)?;
+
+        Ok(Stamp {
+            header_name: "X-Stamp",
+            header_value: self.engine.encode(json_stamp),
+        })
+    }
+}
+
+/// A WebAuthn assertion produced by a passkey, with every field base64url-encoded.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebauthnAssertion {
+    pub credential_id: String,
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
+
+/// The source of passkey assertions used by `WebauthnStamper`.
+///
+/// It is called with the challenge of each request and returns the assertion of the
+/// passkey over it.
+pub type WebauthnAssertionProvider =
+    Arc<dyn Fn(&str) -> TurnkeyResult<WebauthnAssertion> + Send + Sync>;
+
+/// Stamps requests with a passkey assertion in the `X-Stamp-Webauthn` header.
+///
+/// The challenge of each request is the hex-encoded SHA-256 digest of its body. Obtaining
+/// the assertion, e.g. from a hardware authenticator, is up to the provider passed to
+/// `WebauthnStamper::new`.
+#[derive(Clone)]
+pub struct WebauthnStamper {
+    get_assertion: WebauthnAssertionProvider,
+}
+
+impl WebauthnStamper {
+    /// Creates a WebAuthn stamper that obtains assertions from `get_assertion`.
+    ///
+    /// # Arguments
+    ///
+    /// * `get_assertion` - Called with the challenge of each request; returns the passkey
+    ///   assertion over it.
+    pub fn new(
+        get_assertion: impl Fn(&str) -> TurnkeyResult<WebauthnAssertion> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            get_assertion: Arc::new(get_assertion),
+        }
+    }
+}
+
+impl Stamper for WebauthnStamper {
+    fn stamp(&self, body: &str) -> TurnkeyResult<Stamp> {
+        let challenge =
+            bytes_to_hex(&Sha256::digest(body.as_bytes())).map_err(TurnkeyError::hex)?;
+        let assertion = (self.get_assertion)(&challenge)?;
+
+        Ok(Stamp {
+            header_name: "X-Stamp-Webauthn",
+            header_value: serde_json::to_string(&assertion)?,
+        })
+    }
+}
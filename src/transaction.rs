@@ -0,0 +1,166 @@
+use {
+    crate::errors::{TurnkeyError, TurnkeyResult},
+    base64::engine::{general_purpose::STANDARD, Engine},
+    solana_sdk::{bs58, pubkey::Pubkey, signature::Signature, transaction::Transaction},
+};
+
+/// Returns the indices of the required signers whose signatures have not been filled in yet.
+///
+/// A signature slot is considered unsigned while it still holds the default signature. Slots
+/// for required signers that are missing from `transaction.signatures` entirely are reported
+/// as unsigned as well.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction to inspect.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     solana_sdk::{message::Message, pubkey::Pubkey, system_instruction, transaction::Transaction},
+///     turnkey::transaction::unsigned_signer_indices,
+/// };
+///
+/// let payer = Pubkey::new_unique();
+/// let instruction = system_instruction::transfer(&payer, &payer, 100);
+/// let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+///
+/// assert_eq!(unsigned_signer_indices(&transaction), vec![0]);
+/// ```
+pub fn unsigned_signer_indices(transaction: &Transaction) -> Vec<usize> {
+    let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+
+    (0..num_required_signatures)
+        .filter(|&i| {
+            transaction
+                .signatures
+                .get(i)
+                .is_none_or(|signature| *signature == Signature::default())
+        })
+        .collect()
+}
+
+/// Returns the fee payer of a transaction.
+///
+/// The fee payer is the first account key of the message. `None` is returned when the
+/// message has no required signatures, since such a transaction has no fee payer.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction to inspect.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     solana_sdk::{message::Message, pubkey::Pubkey, system_instruction, transaction::Transaction},
+///     turnkey::transaction::fee_payer,
+/// };
+///
+/// let payer = Pubkey::new_unique();
+/// let recipient = Pubkey::new_unique();
+/// let instruction = system_instruction::transfer(&payer, &recipient, 100);
+/// let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+///
+/// assert_eq!(fee_payer(&transaction), Some(payer));
+/// ```
+pub fn fee_payer(transaction: &Transaction) -> Option<Pubkey> {
+    if transaction.message.header.num_required_signatures == 0 {
+        return None;
+    }
+
+    transaction.message.account_keys.first().copied()
+}
+
+/// Returns the signature slot index of `pubkey` if it is a required signer of the transaction.
+///
+/// `None` is returned when `pubkey` is not one of the message's required signers. The slot
+/// may not be allocated yet; see `ensure_signature_slots`.
+pub(crate) fn required_signer_index(transaction: &Transaction, pubkey: &Pubkey) -> Option<usize> {
+    let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+
+    transaction
+        .message
+        .account_keys
+        .iter()
+        .position(|key| key == pubkey)
+        .filter(|&i| i < num_required_signatures)
+}
+
+/// Pads the signatures of a transaction with default signatures up to its number of required
+/// signatures.
+///
+/// Transactions built without `Transaction::new_unsigned`, e.g. from a bare `Message` with an
+/// empty `signatures` vector, have no slots to insert signatures into until this is called.
+pub(crate) fn ensure_signature_slots(transaction: &mut Transaction) {
+    let num_required_signatures = transaction.message.header.num_required_signatures as usize;
+    if transaction.signatures.len() < num_required_signatures {
+        transaction
+            .signatures
+            .resize(num_required_signatures, Signature::default());
+    }
+}
+
+/// Controls what signing does when the target signature slot already holds a signature.
+///
+/// A slot counts as signed whenever it holds anything other than the default signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignaturePolicy {
+    /// Sign and replace the existing signature.
+    #[default]
+    Overwrite,
+    /// Leave the transaction untouched and return the existing signature without signing.
+    SkipIfPresent,
+    /// Return an error without signing.
+    ErrorIfPresent,
+}
+
+/// The text encoding of a serialized transaction, as expected by different broadcast paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionEncoding {
+    /// Standard, padded base64, as accepted by the JSON RPC `sendTransaction` method.
+    Base64,
+    /// Base58, as used by some legacy tooling.
+    Base58,
+}
+
+/// Serializes a transaction into its wire format and encodes it as text.
+///
+/// # Arguments
+///
+/// * `transaction` - The transaction to encode.
+/// * `encoding` - The `TransactionEncoding` to encode the serialized transaction with.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     solana_sdk::{message::Message, pubkey::Pubkey, system_instruction, transaction::Transaction},
+///     turnkey::transaction::{encode_transaction, TransactionEncoding},
+/// };
+///
+/// let payer = Pubkey::new_unique();
+/// let instruction = system_instruction::transfer(&payer, &payer, 100);
+/// let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(&payer)));
+///
+/// let encoded = encode_transaction(&transaction, TransactionEncoding::Base64).unwrap();
+/// assert!(encoded.starts_with("AQ"));
+/// ```
+pub fn encode_transaction(
+    transaction: &Transaction,
+    encoding: TransactionEncoding,
+) -> TurnkeyResult<String> {
+    let serialized_transaction = serialize_transaction(transaction)?;
+
+    Ok(match encoding {
+        TransactionEncoding::Base64 => STANDARD.encode(serialized_transaction),
+        TransactionEncoding::Base58 => bs58::encode(serialized_transaction).into_string(),
+    })
+}
+
+/// Serializes a transaction into its wire format.
+pub(crate) fn serialize_transaction(transaction: &Transaction) -> TurnkeyResult<Vec<u8>> {
+    bincode::serialize(transaction)
+        .map_err(|e| TurnkeyError::OtherError(format!("Failed to serialize transaction: {}", e)))
+}
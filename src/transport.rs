@@ -0,0 +1,81 @@
+use {
+    crate::errors::TurnkeyResult,
+    async_trait::async_trait,
+    reqwest::{header::HeaderMap, Client, StatusCode},
+};
+
+/// A raw HTTP response returned by a `TurnkeyTransport`.
+///
+/// The body is kept as text so the `Turnkey` client can decide how to deserialize it
+/// depending on the status code.
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// The HTTP layer used by the `Turnkey` client to reach the Turnkey API.
+///
+/// The default implementation, `ReqwestTransport`, sends requests with `reqwest`.
+/// Implementing this trait allows the client to be driven by a fake transport that
+/// returns canned responses, which makes it possible to test code built on top of
+/// `Turnkey` without network access or valid credentials.
+#[async_trait]
+pub trait TurnkeyTransport: Send + Sync {
+    /// Sends a `POST` request with the given headers and body to `url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The full URL of the API endpoint.
+    /// * `headers` - The headers to attach to the request, including the `X-Stamp`.
+    /// * `body` - The serialized JSON request body.
+    ///
+    async fn post(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        body: String,
+    ) -> TurnkeyResult<TransportResponse>;
+}
+
+/// The default `TurnkeyTransport`, backed by a `reqwest::Client`.
+#[derive(Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport that sends requests with the given `reqwest::Client`.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TurnkeyTransport for ReqwestTransport {
+    async fn post(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        body: String,
+    ) -> TurnkeyResult<TransportResponse> {
+        let response = self
+            .client
+            .post(url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use {
+    crate::{
+        bytes::hex_to_bytes,
+        errors::{TurnkeyError, TurnkeyResult},
+        models::ApiStamp,
+    },
+    base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine},
+    p256::ecdsa::{self, signature::Verifier, VerifyingKey},
+    solana_sdk::{pubkey::Pubkey, signature::Signature},
+    std::str::FromStr,
+};
+
+/// Verifies an ed25519 signature over `message`, without a configured `Turnkey` client.
+///
+/// Returns `Ok(false)` if the signature is well-formed but does not verify against the
+/// public key.
+///
+/// # Arguments
+///
+/// * `signature` - The base58-encoded signature.
+/// * `pubkey` - The base58-encoded public key of the signer.
+/// * `message` - The signed message bytes.
+///
+/// # Errors
+///
+/// Returns `TurnkeyError::SignatureError` if `signature` is not a valid base58 signature, or
+/// `TurnkeyError::OtherError` if `pubkey` is not a valid base58 public key.
+///
+/// # Examples
+///
+/// ```
+/// use {
+///     solana_sdk::signature::{Keypair, Signer},
+///     turnkey::verify::verify_signature,
+/// };
+///
+/// let keypair = Keypair::new();
+/// let signature = keypair.sign_message(b"hello");
+/// let pubkey = keypair.pubkey().to_string();
+///
+/// assert!(verify_signature(&signature.to_string(), &pubkey, b"hello").unwrap());
+/// assert!(!verify_signature(&signature.to_string(), &pubkey, b"goodbye").unwrap());
+/// ```
+pub fn verify_signature(signature: &str, pubkey: &str, message: &[u8]) -> TurnkeyResult<bool> {
+    let signature = Signature::from_str(signature)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid signature: {}", e)))?;
+    let pubkey = Pubkey::from_str(pubkey)?;
+
+    Ok(signature.verify(pubkey.as_ref(), message))
+}
+
+/// Verifies an `X-Stamp` header against a request body and an API public key.
+///
+/// This is the reverse of `ApiKeyStamper::stamp`: the stamp is base64url-decoded, parsed as
+/// API stamp JSON, and its DER-encoded signature is verified over `body`. Returns
+/// `Ok(false)` if the stamp is well-formed but was not produced by `public_key_hex` over
+/// `body`, including when it names a different public key.
+///
+/// # Arguments
+///
+/// * `public_key_hex` - The hex-encoded, SEC1-compressed public half of the API key.
+/// * `body` - The exact request body the stamp was computed over.
+/// * `encoded_stamp` - The value of the `X-Stamp` header.
+///
+/// # Errors
+///
+/// Returns `TurnkeyError::SignatureError` if the stamp or the public key is malformed.
+pub fn verify_stamp(public_key_hex: &str, body: &str, encoded_stamp: &str) -> TurnkeyResult<bool> {
+    let public_key_bytes = hex_to_bytes(public_key_hex)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid API public key: {}", e)))?;
+    let verifying_key = VerifyingKey::from_sec1_bytes(&public_key_bytes)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid API public key: {}", e)))?;
+
+    let json_stamp = URL_SAFE_NO_PAD
+        .decode(encoded_stamp)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid stamp encoding: {}", e)))?;
+    let stamp: ApiStamp = serde_json::from_slice(&json_stamp)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid stamp: {}", e)))?;
+    if !stamp.public_key.eq_ignore_ascii_case(public_key_hex) {
+        return Ok(false);
+    }
+
+    let signature_der = hex_to_bytes(&stamp.signature)
+        .map_err(|e| TurnkeyError::SignatureError(format!("Invalid stamp signature: {}", e)))?;
+    let signature = ecdsa::Signature::from_der(&signature_der)?;
+
+    Ok(verifying_key.verify(body.as_bytes(), &signature).is_ok())
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "blocking")]
+
+use {
+    async_trait::async_trait,
+    reqwest::{header::HeaderMap, StatusCode},
+    solana_sdk::{
+        message::Message, pubkey::Pubkey, signature::Signature, system_instruction,
+        transaction::Transaction,
+    },
+    std::env,
+    turnkey::{
+        blocking::Turnkey,
+        errors::TurnkeyResult,
+        transport::{TransportResponse, TurnkeyTransport},
+        KeySelector,
+    },
+};
+
+const SIGNATURE_R: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+const SIGNATURE_S: &str = "0202020202020202020202020202020202020202020202020202020202020202";
+
+/// A transport that always replies with a completed signing activity.
+struct SignedTransport;
+
+#[async_trait]
+impl TurnkeyTransport for SignedTransport {
+    async fn post(
+        &self,
+        _url: &str,
+        _headers: HeaderMap,
+        _body: String,
+    ) -> TurnkeyResult<TransportResponse> {
+        Ok(TransportResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: format!(
+                r#"{{"activity":{{"id":"test-activity","organizationId":"test-org","status":"ACTIVITY_STATUS_COMPLETED","fingerprint":"test-fingerprint","type":"ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2","result":{{"signRawPayloadResult":{{"r":"{}","s":"{}","v":"00"}}}}}}}}"#,
+                SIGNATURE_R, SIGNATURE_S
+            ),
+        })
+    }
+}
+
+#[test]
+fn test_blocking_sign_transaction() -> TurnkeyResult<()> {
+    let example_pubkey = Pubkey::new_from_array([7; 32]);
+    env::set_var("TURNKEY_API_PUBLIC_KEY", "02test");
+    env::set_var(
+        "TURNKEY_API_PRIVATE_KEY",
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+    );
+    env::set_var("TURNKEY_ORGANIZATION_ID", "test-org");
+    env::set_var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID", "test-private-key");
+    env::set_var("TURNKEY_EXAMPLE_PUBLIC_KEY", example_pubkey.to_string());
+    let turnkey_client = Turnkey::new_without_dotenv()?.with_transport(SignedTransport);
+
+    let instruction = system_instruction::transfer(&example_pubkey, &example_pubkey, 100);
+    let message = Message::new(&[instruction], Some(&example_pubkey));
+    let mut transaction = Transaction::new_unsigned(message);
+    let signature = turnkey_client.sign_transaction(&mut transaction, KeySelector::ExampleKey)?;
+
+    let mut expected = [1u8; 64];
+    expected[32..].fill(2);
+    assert_eq!(signature, Signature::from(expected));
+    assert_eq!(transaction.signatures[0], signature);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use {
+    std::env,
+    turnkey::{errors::TurnkeyError, Turnkey},
+};
+
+#[test]
+fn test_new_without_dotenv_reports_missing_variable() {
+    env::set_var("TURNKEY_API_PUBLIC_KEY", "02env");
+    env::set_var(
+        "TURNKEY_API_PRIVATE_KEY",
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+    );
+    env::set_var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID", "env-private-key");
+    env::set_var(
+        "TURNKEY_EXAMPLE_PUBLIC_KEY",
+        "11111111111111111111111111111111",
+    );
+    env::remove_var("TURNKEY_ORGANIZATION_ID");
+
+    match Turnkey::new_without_dotenv() {
+        Err(TurnkeyError::MissingConfig(message)) => {
+            assert_eq!(message, "missing TURNKEY_ORGANIZATION_ID")
+        }
+        _ => panic!("Expected a missing config error"),
+    }
+
+    env::set_var("TURNKEY_ORGANIZATION_ID", "env-org");
+    let status = Turnkey::new_without_dotenv()
+        .expect("Failed to build client")
+        .public_status();
+    assert_eq!(status.organization_id, "env-org");
+}
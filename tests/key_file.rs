@@ -0,0 +1,134 @@
+use {
+    std::{env, fs, path::PathBuf},
+    turnkey::{config::SecretBundle, errors::TurnkeyError, Turnkey},
+};
+
+fn write_key_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("turnkey-{}-{}", std::process::id(), name));
+    fs::write(&path, contents).expect("Failed to write key file");
+    path
+}
+
+#[test]
+fn test_from_json_key_file() {
+    let path = write_key_file(
+        "keys.json",
+        r#"{
+            "organizationId": "file-org",
+            "apiPublicKey": "02file",
+            "apiPrivateKey": "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+            "exampleKey": {
+                "privateKeyId": "file-private-key",
+                "publicKey": "11111111111111111111111111111111"
+            }
+        }"#,
+    );
+
+    let status = Turnkey::from_key_file(&path)
+        .expect("Failed to load key file")
+        .public_status();
+    assert_eq!(status.organization_id, "file-org");
+    assert_eq!(status.key_labels, vec!["example".to_string()]);
+
+    fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_from_toml_key_file_without_signing_keys() {
+    let path = write_key_file(
+        "keys.toml",
+        r#"
+            organization_id = "toml-org"
+            api_public_key = "02file"
+            api_private_key = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721"
+        "#,
+    );
+
+    let status = Turnkey::from_key_file(&path)
+        .expect("Failed to load key file")
+        .public_status();
+    assert_eq!(status.organization_id, "toml-org");
+    assert_eq!(status.key_count, 0);
+
+    fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_from_key_file_missing_field() {
+    let path = write_key_file(
+        "missing.json",
+        r#"{ "organizationId": "file-org", "apiPublicKey": "02file" }"#,
+    );
+
+    match Turnkey::from_key_file(&path) {
+        Err(TurnkeyError::OtherError(message)) => {
+            assert!(message.contains("apiPrivateKey") || message.contains("api_private_key"))
+        }
+        _ => panic!("Expected an invalid key file error"),
+    }
+
+    fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_from_key_file_not_found() {
+    let result = Turnkey::from_key_file(env::temp_dir().join("turnkey-does-not-exist.json"));
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+}
+
+#[test]
+fn test_from_secret_bundle() {
+    let bundle: SecretBundle = serde_json::from_str(
+        r#"{
+            "organizationId": "bundle-org",
+            "apiKeys": [
+                { "publicKey": "02primary", "privateKey": "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721" },
+                { "publicKey": "02backup", "privateKey": "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721" }
+            ],
+            "keys": {
+                "example": { "privateKeyId": "bundle-private-key", "publicKey": "11111111111111111111111111111111" }
+            }
+        }"#,
+    )
+    .expect("Failed to parse secret bundle");
+
+    let status = Turnkey::from_secret_bundle(bundle)
+        .expect("Failed to build client")
+        .public_status();
+    assert_eq!(status.organization_id, "bundle-org");
+    assert_eq!(status.key_labels, vec!["example".to_string()]);
+    assert_eq!(status.api_key_count, 2);
+}
+
+#[test]
+fn test_from_key_file_rejects_short_private_key() {
+    let path = write_key_file(
+        "short.json",
+        r#"{ "organizationId": "file-org", "apiPublicKey": "02file", "apiPrivateKey": "c9afa9d8" }"#,
+    );
+
+    match Turnkey::from_key_file(&path) {
+        Err(TurnkeyError::OtherError(message)) => assert!(message.contains("32 bytes")),
+        _ => panic!("Expected an invalid private key error"),
+    }
+
+    fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_from_key_file_rejects_odd_length_private_key() {
+    let path = write_key_file(
+        "odd.json",
+        &format!(
+            r#"{{ "organizationId": "file-org", "apiPublicKey": "02file", "apiPrivateKey": "{}" }}"#,
+            "a".repeat(63)
+        ),
+    );
+
+    match Turnkey::from_key_file(&path) {
+        Err(TurnkeyError::OtherError(message)) => assert!(message.contains("not valid hex")),
+        _ => panic!("Expected an invalid private key error"),
+    }
+
+    fs::remove_file(path).ok();
+}
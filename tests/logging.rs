@@ -0,0 +1,11 @@
+use turnkey::logging::StampLogging;
+
+#[test]
+fn test_stamp_redaction_levels() {
+    let stamp = "eyJwdWJsaWNLZXkiOiIwMnRlc3QifQ";
+
+    assert_eq!(StampLogging::default(), StampLogging::None);
+    assert!(!StampLogging::None.redact(stamp).contains("eyJ"));
+    assert_eq!(StampLogging::Prefix.redact(stamp), "eyJwdWJs...");
+    assert_eq!(StampLogging::Full.redact(stamp), stamp);
+}
@@ -0,0 +1,22 @@
+use {
+    std::time::Duration,
+    turnkey::retry::{Jitter, RetryPolicy},
+};
+
+#[test]
+fn test_retry_delay_jitter_bounds() {
+    let policy = |jitter| RetryPolicy {
+        max_retries: 3,
+        backoff: Duration::from_millis(100),
+        timeout: None,
+        jitter,
+    };
+
+    assert_eq!(policy(Jitter::None).delay(2), Duration::from_millis(400));
+    for _ in 0..100 {
+        assert!(policy(Jitter::Full).delay(2) <= Duration::from_millis(400));
+
+        let equal = policy(Jitter::Equal).delay(2);
+        assert!(equal >= Duration::from_millis(200) && equal <= Duration::from_millis(400));
+    }
+}
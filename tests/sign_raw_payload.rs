@@ -42,14 +42,14 @@ async fn test_example_sign_raw_payload() -> TurnkeyResult<()> {
     );
 
     // Sign the transaction with the Turnkey client
-    let (tx, _sig) = turnkey_client
+    turnkey_client
         .sign_transaction(&mut transaction, KeySelector::ExampleKey)
         .await?;
 
     // Send the transaction and confirm it
     // This also acts as a runtime check to ensure the transaction was successfully signed and accepted
     let tx_sig = rpc
-        .send_and_confirm_transaction(&tx)
+        .send_and_confirm_transaction(&transaction)
         .expect("Failed to send and confirm transaction");
 
     // Verify the transaction signature has been updated to the actual signature
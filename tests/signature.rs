@@ -0,0 +1,101 @@
+//! Deterministic test vectors for request stamping and signature assembly.
+//!
+//! The API key and the "sample" message are taken from RFC 6979, appendix A.2.5 (P-256 with
+//! SHA-256), so the expected signature values come from the RFC rather than this crate.
+
+use {
+    base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine},
+    turnkey::{
+        errors::TurnkeyResult,
+        signature::assemble_signature,
+        stamp::{ApiKeyStamper, Stamper},
+        verify::verify_stamp,
+    },
+};
+
+/// Encodes bytes as lowercase hex.
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes a hex string that is known to be valid.
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const RFC6979_PRIVATE_KEY: &str =
+    "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+const RFC6979_PUBLIC_KEY: &str =
+    "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+const RFC6979_MESSAGE: &str = "sample";
+const RFC6979_R: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716";
+const RFC6979_S: &str = "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+/// The `X-Stamp` header of `RFC6979_MESSAGE` stamped with the RFC 6979 key.
+const EXPECTED_STAMP: &str = "eyJwdWJsaWNLZXkiOiIwMzYwZmVkNGJhMjU1YTlkMzFjOTYxZWI3NGM2MzU2ZDY4YzA0OWI4OTIzYjYxZmE2Y2U2Njk2MjJlNjBmMjlmYjYiLCJzaWduYXR1cmUiOiIzMDQ2MDIyMTAwZWZkNDhiMmFhY2I2YThmZDExNDBkZDljZDQ1ZTgxZDY5ZDJjODc3YjU2YWFmOTkxYzM0ZDBlYTg0ZWFmMzcxNjAyMjEwMGY3Y2IxYzk0MmQ2NTdjNDFkNDM2YzdhMWI2ZTI5ZjY1ZjNlOTAwZGJiOWFmZjQwNjRkYzRhYjJmODQzYWNkYTgiLCJzY2hlbWUiOiJTSUdOQVRVUkVfU0NIRU1FX1RLX0FQSV9QMjU2In0";
+
+#[test]
+fn test_stamp_matches_test_vector() -> TurnkeyResult<()> {
+    let stamper = ApiKeyStamper::new(RFC6979_PUBLIC_KEY, RFC6979_PRIVATE_KEY)?;
+
+    let stamp = stamper.stamp(RFC6979_MESSAGE)?;
+    assert_eq!(stamp.header_value, EXPECTED_STAMP);
+    assert!(verify_stamp(
+        RFC6979_PUBLIC_KEY,
+        RFC6979_MESSAGE,
+        &stamp.header_value
+    )?);
+
+    // the DER-encoded signature holds the RFC's r and s, each with a leading zero byte
+    let stamp: serde_json::Value =
+        serde_json::from_slice(&URL_SAFE_NO_PAD.decode(&stamp.header_value).unwrap())?;
+    assert_eq!(
+        stamp["signature"],
+        format!("3046022100{}022100{}", RFC6979_R, RFC6979_S)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_assemble_signature_matches_test_vector() -> TurnkeyResult<()> {
+    let signature = assemble_signature(RFC6979_R, RFC6979_S)?;
+
+    assert_eq!(signature.len(), 64);
+    assert_eq!(signature[..32], hex_to_bytes(RFC6979_R));
+    assert_eq!(signature[32..], hex_to_bytes(RFC6979_S));
+    assert_eq!(
+        bytes_to_hex(&signature),
+        format!("{}{}", RFC6979_R, RFC6979_S)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_assemble_signature_rejects_invalid_hex() {
+    assert!(assemble_signature("zz", RFC6979_S).is_err());
+}
+
+#[test]
+fn test_assemble_signature_pads_short_components() -> TurnkeyResult<()> {
+    // an `r` value with a leading zero byte, returned without it
+    let short_r = &RFC6979_R[2..];
+
+    let signature = assemble_signature(short_r, RFC6979_S)?;
+    assert_eq!(signature.len(), 64);
+    assert_eq!(signature[0], 0);
+    assert_eq!(signature[1..32], hex_to_bytes(short_r));
+    assert_eq!(signature[32..], hex_to_bytes(RFC6979_S));
+
+    // odd-length values are padded to a whole number of bytes as well
+    let signature = assemble_signature("1", RFC6979_S)?;
+    assert_eq!(signature[..32], [[0; 31].as_slice(), &[1]].concat());
+
+    assert!(assemble_signature(&format!("00{}", RFC6979_R), RFC6979_S).is_err());
+
+    Ok(())
+}
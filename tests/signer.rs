@@ -0,0 +1,79 @@
+use {
+    async_trait::async_trait,
+    solana_sdk::{
+        message::Message,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    std::{env, sync::Arc},
+    turnkey::{
+        errors::{TurnkeyError, TurnkeyResult},
+        signer::TransactionSigner,
+        KeySelector, Turnkey,
+    },
+};
+
+/// A signer that signs with a local keypair, standing in for `Turnkey` in tests.
+struct MockSigner {
+    keypair: Keypair,
+}
+
+#[async_trait]
+impl TransactionSigner for MockSigner {
+    async fn sign_transaction(
+        &self,
+        transaction: &mut Transaction,
+        _key_selector: KeySelector,
+    ) -> TurnkeyResult<Signature> {
+        transaction
+            .try_partial_sign(&[&self.keypair], transaction.message.recent_blockhash)
+            .map_err(|e| TurnkeyError::OtherError(e.to_string()))?;
+        Ok(transaction.signatures[0])
+    }
+}
+
+/// Downstream code that only depends on the `TransactionSigner` abstraction.
+async fn sign_transfer(
+    signer: Arc<dyn TransactionSigner>,
+    payer: &Pubkey,
+) -> TurnkeyResult<Transaction> {
+    let instruction = system_instruction::transfer(payer, payer, 100);
+    let mut transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    signer
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+    Ok(transaction)
+}
+
+#[tokio::test]
+async fn test_mock_transaction_signer() -> TurnkeyResult<()> {
+    let keypair = Keypair::new();
+    let payer = keypair.pubkey();
+    let signer: Arc<dyn TransactionSigner> = Arc::new(MockSigner { keypair });
+
+    let transaction = sign_transfer(signer, &payer).await?;
+    transaction.verify().expect("Signature should verify");
+
+    Ok(())
+}
+
+#[test]
+fn test_turnkey_is_a_transaction_signer() -> TurnkeyResult<()> {
+    env::set_var("TURNKEY_API_PUBLIC_KEY", "02test");
+    env::set_var(
+        "TURNKEY_API_PRIVATE_KEY",
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+    );
+    env::set_var("TURNKEY_ORGANIZATION_ID", "test-org");
+    env::set_var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID", "test-private-key");
+    env::set_var(
+        "TURNKEY_EXAMPLE_PUBLIC_KEY",
+        "11111111111111111111111111111111",
+    );
+
+    let _signer: Arc<dyn TransactionSigner> = Arc::new(Turnkey::new_without_dotenv()?);
+
+    Ok(())
+}
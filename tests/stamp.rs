@@ -0,0 +1,92 @@
+use {
+    base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine},
+    p256::ecdsa::SigningKey,
+    turnkey::{
+        errors::{TurnkeyError, TurnkeyResult},
+        models::ApiStamp,
+        stamp::{ApiKeyStamper, Stamper},
+        verify::verify_stamp,
+    },
+};
+
+/// Encodes bytes as lowercase hex.
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes a hex string that is known to be valid.
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const TEST_API_PRIVATE_KEY: &str =
+    "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+
+fn test_api_public_key() -> String {
+    let signing_key = SigningKey::from_bytes(&hex_to_bytes(TEST_API_PRIVATE_KEY)).unwrap();
+    let public_key = signing_key.verifying_key().to_encoded_point(true);
+    bytes_to_hex(public_key.as_bytes())
+}
+
+#[test]
+fn test_stamp_round_trip() -> TurnkeyResult<()> {
+    let public_key = test_api_public_key();
+    let stamper = ApiKeyStamper::new(public_key.clone(), TEST_API_PRIVATE_KEY)?;
+    let body = r#"{"organizationId":"test-org"}"#;
+
+    let stamp = stamper.stamp(body)?;
+    assert_eq!(stamp.header_name, "X-Stamp");
+    assert!(verify_stamp(&public_key, body, &stamp.header_value)?);
+    assert!(!verify_stamp(
+        &public_key,
+        r#"{"organizationId":"other-org"}"#,
+        &stamp.header_value
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_stamp_rejects_other_public_key() -> TurnkeyResult<()> {
+    let stamper = ApiKeyStamper::new("02test", TEST_API_PRIVATE_KEY)?;
+    let body = r#"{"organizationId":"test-org"}"#;
+
+    let stamp = stamper.stamp(body)?;
+    assert!(!verify_stamp(
+        &test_api_public_key(),
+        body,
+        &stamp.header_value
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_stamp_rejects_malformed_input() -> TurnkeyResult<()> {
+    let public_key = test_api_public_key();
+    let stamper = ApiKeyStamper::new(public_key.clone(), TEST_API_PRIVATE_KEY)?;
+    let body = r#"{"organizationId":"test-org"}"#;
+    let stamp = stamper.stamp(body)?;
+
+    for malformed_public_key in ["abc", "zz", "é"] {
+        assert!(matches!(
+            verify_stamp(malformed_public_key, body, &stamp.header_value),
+            Err(TurnkeyError::SignatureError(_))
+        ));
+    }
+
+    let malformed_stamp = URL_SAFE_NO_PAD.encode(serde_json::to_string(&ApiStamp {
+        public_key: public_key.clone(),
+        signature: "304".to_string(),
+        scheme: "SIGNATURE_SCHEME_TK_API_P256".to_string(),
+    })?);
+    assert!(matches!(
+        verify_stamp(&public_key, body, &malformed_stamp),
+        Err(TurnkeyError::SignatureError(_))
+    ));
+
+    Ok(())
+}
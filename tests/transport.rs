@@ -0,0 +1,1751 @@
+use {
+    async_trait::async_trait,
+    base64::engine::{general_purpose::URL_SAFE_NO_PAD, Engine},
+    reqwest::{
+        header::{HeaderMap, HeaderValue, CONTENT_TYPE, USER_AGENT},
+        StatusCode,
+    },
+    sha2::{Digest, Sha256},
+    solana_sdk::{
+        message::Message,
+        offchain_message::OffchainMessage,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    std::{
+        collections::HashMap,
+        env, fs,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    },
+    turnkey::{
+        audit::SIGNATURE_ALREADY_PRESENT,
+        errors::{TurnkeyError, TurnkeyResult},
+        models::{
+            ApiKeyParams, ApiVersion, CreateSubOrgParams, RootUserParams, WalletAccountParams,
+            WalletParams,
+        },
+        polling::EscalationDecision,
+        retry::{Jitter, RetryPolicy},
+        stamp::{WebauthnAssertion, WebauthnStamper},
+        transaction::{SignaturePolicy, TransactionEncoding},
+        transport::{TransportResponse, TurnkeyTransport},
+        Curve, KeySelector, Turnkey,
+    },
+};
+
+/// Encodes bytes as lowercase hex.
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+const TEST_API_PRIVATE_KEY: &str =
+    "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+const SIGNATURE_R: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+const SIGNATURE_S: &str = "0202020202020202020202020202020202020202020202020202020202020202";
+
+/// A transport that records every request and replies with canned responses.
+///
+/// Responses are returned in order, with the last one repeated once the sequence runs out.
+#[derive(Clone)]
+struct FakeTransport {
+    responses: Vec<(StatusCode, String)>,
+    response_headers: HeaderMap,
+    requests: Arc<Mutex<Vec<(String, HeaderMap, String)>>>,
+}
+
+impl FakeTransport {
+    fn new(status: StatusCode, body: &str) -> Self {
+        Self::sequence(vec![(status, body)])
+    }
+
+    fn sequence(responses: Vec<(StatusCode, &str)>) -> Self {
+        Self {
+            responses: responses
+                .into_iter()
+                .map(|(status, body)| (status, body.to_string()))
+                .collect(),
+            response_headers: HeaderMap::new(),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn with_content_type(mut self, content_type: &'static str) -> Self {
+        self.response_headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        self
+    }
+}
+
+#[async_trait]
+impl TurnkeyTransport for FakeTransport {
+    async fn post(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        body: String,
+    ) -> TurnkeyResult<TransportResponse> {
+        let mut requests = self.requests.lock().unwrap();
+        requests.push((url.to_string(), headers, body));
+        let (status, body) = &self.responses[(requests.len() - 1).min(self.responses.len() - 1)];
+
+        Ok(TransportResponse {
+            status: *status,
+            headers: self.response_headers.clone(),
+            body: body.clone(),
+        })
+    }
+}
+
+/// A transport that tracks how many requests are in flight at once.
+#[derive(Clone, Default)]
+struct ConcurrencyTrackingTransport {
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl TurnkeyTransport for ConcurrencyTrackingTransport {
+    async fn post(
+        &self,
+        _url: &str,
+        _headers: HeaderMap,
+        _body: String,
+    ) -> TurnkeyResult<TransportResponse> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        Ok(TransportResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: signed_activity_json(),
+        })
+    }
+}
+
+/// A transport that responds slowly and records the age of each request's timestamp on arrival.
+#[derive(Clone, Default)]
+struct SlowTransport {
+    timestamp_ages_ms: Arc<Mutex<Vec<i64>>>,
+}
+
+#[async_trait]
+impl TurnkeyTransport for SlowTransport {
+    async fn post(
+        &self,
+        _url: &str,
+        _headers: HeaderMap,
+        body: String,
+    ) -> TurnkeyResult<TransportResponse> {
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let timestamp_ms: i64 = body["timestampMs"].as_str().unwrap().parse().unwrap();
+        let age_ms = chrono::Utc::now().timestamp_millis() - timestamp_ms;
+        self.timestamp_ages_ms.lock().unwrap().push(age_ms);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        Ok(TransportResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: signed_activity_json(),
+        })
+    }
+}
+
+fn decode_stamp(headers: &HeaderMap) -> serde_json::Value {
+    let stamp = URL_SAFE_NO_PAD
+        .decode(headers["X-Stamp"].to_str().unwrap())
+        .unwrap();
+    serde_json::from_slice(&stamp).unwrap()
+}
+
+fn stamp_public_key(headers: &HeaderMap) -> String {
+    decode_stamp(headers)["publicKey"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+fn example_pubkey() -> Pubkey {
+    Pubkey::new_from_array([7; 32])
+}
+
+fn test_client(transport: impl TurnkeyTransport + 'static) -> TurnkeyResult<Turnkey> {
+    env::set_var("TURNKEY_API_PUBLIC_KEY", "02test");
+    env::set_var("TURNKEY_API_PRIVATE_KEY", TEST_API_PRIVATE_KEY);
+    env::set_var("TURNKEY_ORGANIZATION_ID", "test-org");
+    env::set_var("TURNKEY_EXAMPLE_PRIVATE_KEY_ID", "test-private-key");
+    env::set_var("TURNKEY_EXAMPLE_PUBLIC_KEY", example_pubkey().to_string());
+
+    Ok(Turnkey::new()?.with_transport(transport))
+}
+
+fn ed25519_test_client(
+    public_key: &Pubkey,
+    transport: impl TurnkeyTransport + 'static,
+) -> TurnkeyResult<Turnkey> {
+    let path = env::temp_dir().join(format!(
+        "turnkey-{}-ed25519-{}.json",
+        std::process::id(),
+        public_key
+    ));
+    let key_file = format!(
+        r#"{{"organizationId":"test-org","apiPublicKey":"02test","apiPrivateKey":"{}","exampleKey":{{"privateKeyId":"test-private-key","publicKey":"{}","curve":"ed25519"}}}}"#,
+        TEST_API_PRIVATE_KEY, public_key
+    );
+    fs::write(&path, key_file).expect("Failed to write key file");
+    let turnkey_client = Turnkey::from_key_file(&path);
+    fs::remove_file(path).ok();
+
+    Ok(turnkey_client?.with_transport(transport))
+}
+
+fn unsigned_transaction() -> Transaction {
+    unsigned_transaction_for(&example_pubkey())
+}
+
+fn unsigned_transaction_for(pubkey: &Pubkey) -> Transaction {
+    let instruction = system_instruction::transfer(pubkey, pubkey, 100);
+    let message = Message::new(&[instruction], Some(pubkey));
+    Transaction::new_unsigned(message)
+}
+
+fn activity_json(status: &str, result: &str) -> String {
+    format!(
+        r#"{{"activity":{{"id":"test-activity","organizationId":"test-org","status":"{}","fingerprint":"test-fingerprint","type":"ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2","result":{}}}}}"#,
+        status, result
+    )
+}
+
+fn signed_activity_json() -> String {
+    signed_activity_json_with(SIGNATURE_R, SIGNATURE_S)
+}
+
+fn signed_activity_json_with(r: &str, s: &str) -> String {
+    let result = format!(
+        r#"{{"signRawPayloadResult":{{"r":"{}","s":"{}","v":"00"}}}}"#,
+        r, s
+    );
+    activity_json("ACTIVITY_STATUS_COMPLETED", &result)
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_fake_transport() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    let mut transaction = unsigned_transaction();
+    let signature = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    let mut expected = [1u8; 64];
+    expected[32..].fill(2);
+    assert_eq!(signature, Signature::from(expected));
+    assert_eq!(transaction.signatures[0], signature);
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, headers, body) = &requests[0];
+    assert!(url.ends_with("/public/v1/submit/sign_raw_payload"));
+    assert!(headers.contains_key("X-Stamp"));
+    assert!(body.contains("ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_pending_activity() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &activity_json("ACTIVITY_STATUS_PENDING", "null"),
+    );
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::ActivityPending(id)) if id == "test-activity"));
+    assert_eq!(transaction.signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_consensus_needed() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &activity_json("ACTIVITY_STATUS_CONSENSUS_NEEDED", "null"),
+    );
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::ConsensusNeeded(id)) if id == "test-activity"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_error_response() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::BAD_REQUEST,
+        r#"{"code":3,"message":"invalid request","details":[]}"#,
+    );
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    match result {
+        Err(TurnkeyError::MethodError(error)) => {
+            assert_eq!(error.code, 3);
+            assert_eq!(error.message, "invalid request");
+        }
+        other => panic!("Expected a method error, got {:?}", other.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_carries_status_and_request_id() -> TurnkeyResult<()> {
+    let mut transport = FakeTransport::new(
+        StatusCode::BAD_REQUEST,
+        r#"{"code":3,"message":"invalid request","details":[]}"#,
+    );
+    transport
+        .response_headers
+        .insert("x-request-id", HeaderValue::from_static("test-request-id"));
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    match result {
+        Err(TurnkeyError::MethodError(error)) => {
+            assert_eq!(error.status, Some(400));
+            assert_eq!(error.request_id.as_deref(), Some("test-request-id"));
+            let message = error.to_string();
+            assert!(message.contains("HTTP status: 400"));
+            assert!(message.contains("request ID: test-request-id"));
+        }
+        other => panic!("Expected a method error, got {:?}", other.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_field_violations_are_validation_errors() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::BAD_REQUEST,
+        r#"{"code":3,"message":"invalid request","details":[{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"parameters.walletName","description":"must not be empty"}]}]}"#,
+    );
+    let turnkey_client = test_client(transport)?;
+
+    let result = turnkey_client.create_wallet("", &[]).await;
+
+    match result {
+        Err(TurnkeyError::Validation { violations, .. }) => {
+            assert_eq!(violations.len(), 1);
+            assert_eq!(violations[0].field, "parameters.walletName");
+            assert_eq!(violations[0].description, "must not be empty");
+        }
+        other => panic!("Expected a validation error, got {:?}", other.map(|_| ())),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_activity_with_fake_transport() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &activity_json("ACTIVITY_STATUS_CONSENSUS_NEEDED", "null"),
+    );
+    let turnkey_client = test_client(transport.clone())?;
+
+    let response = turnkey_client.get_activity("test-activity").await?;
+    assert_eq!(response.activity.id, "test-activity");
+    assert_eq!(response.activity.status, "ACTIVITY_STATUS_CONSENSUS_NEEDED");
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[0];
+    assert!(url.ends_with("/public/v1/query/get_activity"));
+    assert!(body.contains(r#""activityId":"test-activity""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_api_key_failover_on_auth_failure() -> TurnkeyResult<()> {
+    let signed_activity = signed_activity_json();
+    let transport = FakeTransport::sequence(vec![
+        (
+            StatusCode::UNAUTHORIZED,
+            r#"{"code":16,"message":"api key disabled","details":[]}"#,
+        ),
+        (StatusCode::OK, &signed_activity),
+    ]);
+    let turnkey_client =
+        test_client(transport.clone())?.with_backup_api_key("02backup", TEST_API_PRIVATE_KEY)?;
+
+    let mut transaction = unsigned_transaction();
+    turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(stamp_public_key(&requests[0].1), "02test");
+    assert_eq!(stamp_public_key(&requests[1].1), "02backup");
+    assert_eq!(requests[0].2, requests[1].2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_scheme() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let default_client = test_client(transport.clone())?;
+    let custom_client =
+        test_client(transport.clone())?.with_signature_scheme("SIGNATURE_SCHEME_TK_API_CUSTOM");
+
+    default_client.get_activity("test-activity").await?;
+    custom_client.get_activity("test-activity").await?;
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(
+        decode_stamp(&requests[0].1)["scheme"],
+        "SIGNATURE_SCHEME_TK_API_P256"
+    );
+    assert_eq!(
+        decode_stamp(&requests[1].1)["scheme"],
+        "SIGNATURE_SCHEME_TK_API_CUSTOM"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_user_agent_and_custom_headers() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let default_client = test_client(transport.clone())?;
+    let custom_client = test_client(transport.clone())?
+        .with_user_agent("payments-service/1.4.0")?
+        .with_header("X-Trace-Source", "payments-service")?
+        .with_header("Content-Type", "text/plain")?;
+
+    default_client.get_activity("test-activity").await?;
+    custom_client.get_activity("test-activity").await?;
+
+    let requests = transport.requests.lock().unwrap();
+    let default_headers = &requests[0].1;
+    assert!(default_headers[USER_AGENT]
+        .to_str()
+        .unwrap()
+        .starts_with("turnkey-rs/"));
+    let custom_headers = &requests[1].1;
+    assert_eq!(custom_headers[USER_AGENT], "payments-service/1.4.0");
+    assert_eq!(custom_headers["X-Trace-Source"], "payments-service");
+    assert_eq!(custom_headers[CONTENT_TYPE], "application/json");
+    assert!(custom_headers.contains_key("X-Stamp"));
+
+    assert!(test_client(transport.clone())?
+        .with_header("Invalid Header", "value")
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_webauthn_stamper() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let challenges = Arc::new(Mutex::new(Vec::new()));
+    let stamper = WebauthnStamper::new({
+        let challenges = challenges.clone();
+        move |challenge| {
+            challenges.lock().unwrap().push(challenge.to_string());
+            Ok(WebauthnAssertion {
+                credential_id: "test-credential".to_string(),
+                client_data_json: "test-client-data".to_string(),
+                authenticator_data: "test-authenticator-data".to_string(),
+                signature: "test-signature".to_string(),
+            })
+        }
+    });
+    let turnkey_client = test_client(transport.clone())?.with_stamper(stamper);
+
+    turnkey_client.get_activity("test-activity").await?;
+
+    let requests = transport.requests.lock().unwrap();
+    let headers = &requests[0].1;
+    assert!(!headers.contains_key("X-Stamp"));
+    let stamp: serde_json::Value =
+        serde_json::from_str(headers["X-Stamp-Webauthn"].to_str().unwrap()).unwrap();
+    assert_eq!(stamp["credentialId"], "test-credential");
+    assert_eq!(stamp["clientDataJson"], "test-client-data");
+    assert_eq!(stamp["authenticatorData"], "test-authenticator-data");
+    assert_eq!(stamp["signature"], "test-signature");
+    assert_eq!(
+        challenges.lock().unwrap()[0],
+        bytes_to_hex(&Sha256::digest(requests[0].2.as_bytes()))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests() -> TurnkeyResult<()> {
+    let transport = ConcurrencyTrackingTransport::default();
+    let turnkey_client = Arc::new(test_client(transport.clone())?.with_max_concurrent_requests(2));
+
+    let handles = (0..6)
+        .map(|_| {
+            let turnkey_client = turnkey_client.clone();
+            tokio::spawn(async move {
+                let mut transaction = unsigned_transaction();
+                turnkey_client
+                    .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+                    .await
+                    .map(|_| ())
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.await.expect("Signing task panicked")?;
+    }
+
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_concurrent_requests_is_clamped() -> TurnkeyResult<()> {
+    for max_concurrent_requests in [0, usize::MAX] {
+        let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+        let turnkey_client =
+            test_client(transport)?.with_max_concurrent_requests(max_concurrent_requests);
+
+        let mut transaction = unsigned_transaction();
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            turnkey_client.sign_transaction(&mut transaction, KeySelector::ExampleKey),
+        )
+        .await
+        .expect("Signing should not wait forever")?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_escalation_keeps_polling_until_completed() -> TurnkeyResult<()> {
+    let pending_activity = activity_json("ACTIVITY_STATUS_CONSENSUS_NEEDED", "null");
+    let signed_activity = signed_activity_json();
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::OK, &pending_activity),
+        (StatusCode::OK, &pending_activity),
+        (StatusCode::OK, &signed_activity),
+    ]);
+    let escalations = Arc::new(AtomicUsize::new(0));
+    let escalations_seen = escalations.clone();
+    let turnkey_client = test_client(transport.clone())?
+        .with_activity_polling(Duration::from_millis(1), Duration::ZERO)
+        .with_escalation_policy(move |_, _| {
+            escalations_seen.fetch_add(1, Ordering::SeqCst);
+            EscalationDecision::KeepPolling
+        });
+
+    let mut transaction = unsigned_transaction();
+    turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(escalations.load(Ordering::SeqCst), 1);
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[1].0.ends_with("/public/v1/query/get_activity"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_escalation_rejects_stalled_activity() -> TurnkeyResult<()> {
+    let pending_activity = activity_json("ACTIVITY_STATUS_CONSENSUS_NEEDED", "null");
+    let rejected_activity = activity_json("ACTIVITY_STATUS_COMPLETED", "null");
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::OK, &pending_activity),
+        (StatusCode::OK, &pending_activity),
+        (StatusCode::OK, &rejected_activity),
+    ]);
+    let turnkey_client = test_client(transport.clone())?
+        .with_activity_polling(Duration::from_millis(1), Duration::ZERO)
+        .with_escalation_policy(|_, _| EscalationDecision::Reject);
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::ActivityRejected(_))));
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[2];
+    assert!(url.ends_with("/public/v1/submit/reject_activity"));
+    assert!(body.contains(r#""fingerprint":"test-fingerprint""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_public_status_excludes_secrets() -> TurnkeyResult<()> {
+    let turnkey_client = test_client(FakeTransport::new(StatusCode::OK, "{}"))?
+        .with_backup_api_key("02backup", TEST_API_PRIVATE_KEY)?;
+
+    let status = turnkey_client.public_status();
+    assert_eq!(status.organization_id, "test-org");
+    assert_eq!(status.key_labels, vec!["example".to_string()]);
+    assert_eq!(status.key_count, 1);
+    assert_eq!(status.api_key_count, 2);
+
+    let status_json = serde_json::to_string(&status)?;
+    assert!(!status_json.contains(TEST_API_PRIVATE_KEY));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configuration_getters() -> TurnkeyResult<()> {
+    let turnkey_client = test_client(FakeTransport::new(StatusCode::OK, "{}"))?
+        .with_backup_api_key("02backup", TEST_API_PRIVATE_KEY)?;
+
+    assert_eq!(turnkey_client.organization_id(), "test-org");
+    assert_eq!(turnkey_client.api_public_key(), "02test");
+    assert_eq!(
+        turnkey_client.public_key(KeySelector::ExampleKey)?,
+        example_pubkey()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_multi_leaves_other_signers_untouched() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    // the fee payer is not managed by Turnkey, the example key signs the transfer
+    let fee_payer = Pubkey::new_unique();
+    let instruction = system_instruction::transfer(&example_pubkey(), &fee_payer, 100);
+    let message = Message::new(&[instruction], Some(&fee_payer));
+    let mut transaction = Transaction::new_unsigned(message);
+
+    let signatures = turnkey_client
+        .sign_transaction_multi(&mut transaction, &[KeySelector::ExampleKey])
+        .await?;
+
+    assert_eq!(signatures.len(), 1);
+    assert_eq!(transaction.signatures[0], Signature::default());
+    assert_eq!(transaction.signatures[1], signatures[0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_multi_rejects_non_signer() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    let fee_payer = Pubkey::new_unique();
+    let instruction = system_instruction::transfer(&fee_payer, &example_pubkey(), 100);
+    let message = Message::new(&[instruction], Some(&fee_payer));
+    let mut transaction = Transaction::new_unsigned(message);
+
+    let result = turnkey_client
+        .sign_transaction_multi(&mut transaction, &[KeySelector::ExampleKey])
+        .await;
+
+    assert!(result.is_err());
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_multi_applies_signature_policy() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let existing = Signature::from([9u8; 64]);
+
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::SkipIfPresent);
+    let mut transaction = unsigned_transaction();
+    transaction.signatures[0] = existing;
+    let signatures = turnkey_client
+        .sign_transaction_multi(&mut transaction, &[KeySelector::ExampleKey])
+        .await?;
+    assert_eq!(signatures, vec![existing]);
+
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::ErrorIfPresent);
+    let result = turnkey_client
+        .sign_transaction_multi(&mut transaction, &[KeySelector::ExampleKey])
+        .await;
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+    assert_eq!(transaction.signatures[0], existing);
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_api_version_renames_request_fields() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let renames = HashMap::from([("signWith".to_string(), "signer".to_string())]);
+    let turnkey_client =
+        test_client(transport.clone())?.with_api_version(ApiVersion::Renamed(renames));
+
+    let mut transaction = unsigned_transaction();
+    turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    let requests = transport.requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2)?;
+    assert_eq!(body["parameters"]["signer"], "test-private-key");
+    assert!(body["parameters"].get("signWith").is_none());
+    assert_eq!(body["organizationId"], "test-org");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_audit() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let message_len = transaction.message_data().len();
+    let (signature, audit_record) = turnkey_client
+        .sign_transaction_with_audit(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(audit_record.organization_id, "test-org");
+    assert_eq!(audit_record.private_key_id, "test-private-key");
+    assert_eq!(audit_record.activity_id.as_deref(), Some("test-activity"));
+    assert_eq!(audit_record.status, "ACTIVITY_STATUS_COMPLETED");
+    assert_eq!(audit_record.signature_base58, signature.to_string());
+    assert_eq!(audit_record.message_hex_len, message_len * 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_audit_applies_signature_policy() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let existing = Signature::from([9u8; 64]);
+
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::SkipIfPresent);
+    let mut transaction = unsigned_transaction();
+    transaction.signatures[0] = existing;
+    let (signature, audit_record) = turnkey_client
+        .sign_transaction_with_audit(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+    assert_eq!(signature, existing);
+    assert_eq!(audit_record.activity_id, None);
+    assert_eq!(audit_record.status, SIGNATURE_ALREADY_PRESENT);
+
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::ErrorIfPresent);
+    let result = turnkey_client
+        .sign_transaction_with_audit(&mut transaction, KeySelector::ExampleKey)
+        .await;
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+    assert_eq!(transaction.signatures[0], existing);
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_ed25519_key() -> TurnkeyResult<()> {
+    let keypair = Keypair::new();
+    let mut transaction = unsigned_transaction_for(&keypair.pubkey());
+    let expected = keypair.sign_message(&transaction.message_data());
+    let signature_hex = expected
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &signed_activity_json_with(&signature_hex[..64], &signature_hex[64..]),
+    );
+    let turnkey_client = ed25519_test_client(&keypair.pubkey(), transport)?;
+
+    let signature = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+    assert_eq!(signature, expected);
+    transaction.verify().expect("Signature should verify");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_rejects_invalid_ed25519_signature() -> TurnkeyResult<()> {
+    let keypair = Keypair::new();
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = ed25519_test_client(&keypair.pubkey(), transport)?;
+
+    let mut transaction = unsigned_transaction_for(&keypair.pubkey());
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::SignatureError(_))));
+    assert_eq!(transaction.signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_at_fixed_timestamp_is_deterministic() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    for _ in 0..2 {
+        let mut transaction = unsigned_transaction();
+        turnkey_client
+            .sign_transaction_at(&mut transaction, KeySelector::ExampleKey, 1_700_000_000_000)
+            .await?;
+    }
+
+    let requests = transport.requests.lock().unwrap();
+    let (_, first_headers, first_body) = &requests[0];
+    let (_, second_headers, second_body) = &requests[1];
+    assert!(first_body.contains(r#""timestampMs":"1700000000000""#));
+    assert_eq!(first_body, second_body);
+    assert_eq!(first_headers["X-Stamp"], second_headers["X-Stamp"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_policy_skip_if_present() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::SkipIfPresent);
+
+    let existing = Signature::from([9u8; 64]);
+    let mut transaction = unsigned_transaction();
+    transaction.signatures[0] = existing;
+    let signature = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(signature, existing);
+    assert_eq!(transaction.signatures[0], existing);
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_policy_error_if_present() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client =
+        test_client(transport.clone())?.with_signature_policy(SignaturePolicy::ErrorIfPresent);
+
+    let mut transaction = unsigned_transaction();
+    transaction.signatures[0] = Signature::from([9u8; 64]);
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_allocates_missing_signature_slots() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = Transaction {
+        signatures: vec![],
+        message: unsigned_transaction().message,
+    };
+    let signature = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(transaction.signatures, vec![signature]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_detached_leaves_transaction_untouched() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let transaction = unsigned_transaction();
+    let signature = turnkey_client
+        .sign_transaction_detached(&transaction, KeySelector::ExampleKey)
+        .await?;
+
+    let mut expected = [1u8; 64];
+    expected[32..].fill(2);
+    assert_eq!(signature, Signature::from(expected));
+    assert_eq!(transaction.signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_message_struct() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    let message = unsigned_transaction().message;
+    let signature = turnkey_client
+        .sign_message_struct(&message, KeySelector::ExampleKey)
+        .await?;
+
+    let mut expected = [1u8; 64];
+    expected[32..].fill(2);
+    assert_eq!(signature, Signature::from(expected));
+
+    let requests = transport.requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+    assert_eq!(
+        body["parameters"]["payload"],
+        bytes_to_hex(&message.serialize())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_message_struct_rejects_non_signer() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    let message = unsigned_transaction_for(&Pubkey::new_unique()).message;
+    let result = turnkey_client
+        .sign_message_struct(&message, KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+    assert!(transport.requests.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_raw_components() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?;
+
+    let components = turnkey_client
+        .sign_raw_components(b"raw payload", KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(components.r, SIGNATURE_R);
+    assert_eq!(components.s, SIGNATURE_S);
+    assert_eq!(components.v.as_deref(), Some("00"));
+    let mut expected = [1u8; 64];
+    expected[32..].fill(2);
+    assert_eq!(components.signature_bytes, expected);
+
+    let requests = transport.requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+    assert_eq!(body["parameters"]["payload"], bytes_to_hex(b"raw payload"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_digest_verifies_against_payload_hash() -> TurnkeyResult<()> {
+    let keypair = Keypair::new();
+    let payload = vec![42u8; 256 * 1024];
+    let digest: [u8; 32] = Sha256::digest(&payload).into();
+    let expected = keypair.sign_message(&digest);
+    let signature_hex = bytes_to_hex(expected.as_ref());
+
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &signed_activity_json_with(&signature_hex[..64], &signature_hex[64..]),
+    );
+    let turnkey_client = ed25519_test_client(&keypair.pubkey(), transport.clone())?;
+
+    let signature = turnkey_client
+        .sign_digest(&digest, KeySelector::ExampleKey)
+        .await?;
+    assert_eq!(signature, expected);
+    assert!(signature.verify(keypair.pubkey().as_ref(), &Sha256::digest(&payload)));
+
+    let requests = transport.requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+    assert_eq!(body["parameters"]["payload"], bytes_to_hex(&digest));
+    assert_eq!(
+        body["parameters"]["hashFunction"],
+        "HASH_FUNCTION_NOT_APPLICABLE"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_payload_size() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport.clone())?.with_max_payload_size(16);
+
+    turnkey_client
+        .sign_raw_components(&[0; 16], KeySelector::ExampleKey)
+        .await?;
+    let result = turnkey_client
+        .sign_raw_components(&[0; 17], KeySelector::ExampleKey)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(TurnkeyError::PayloadTooLarge {
+            size: 17,
+            max_size: 16
+        })
+    ));
+    assert_eq!(transport.requests.lock().unwrap().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_activity_type_verification_rejects_mismatch() -> TurnkeyResult<()> {
+    let response = signed_activity_json().replace(
+        "ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2",
+        "ACTIVITY_TYPE_SIGN_TRANSACTION_V2",
+    );
+    let transport = FakeTransport::new(StatusCode::OK, &response);
+    let turnkey_client = test_client(transport)?.with_activity_type_verification(true);
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+
+    assert!(
+        matches!(result, Err(TurnkeyError::OtherError(message)) if message.contains("ACTIVITY_TYPE_SIGN_TRANSACTION_V2"))
+    );
+    assert_eq!(transaction.signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_call_retry_policy_overrides_default() -> TurnkeyResult<()> {
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::OK, &signed_activity_json()),
+    ]);
+    let turnkey_client = test_client(transport.clone())?;
+
+    let mut transaction = unsigned_transaction();
+    let result = turnkey_client
+        .sign_transaction(&mut transaction, KeySelector::ExampleKey)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(transport.requests.lock().unwrap().len(), 1);
+
+    let retry_policy = RetryPolicy {
+        max_retries: 3,
+        backoff: Duration::from_millis(1),
+        timeout: Some(Duration::from_secs(5)),
+        jitter: Jitter::Full,
+    };
+    turnkey_client
+        .sign_transaction_with_retry_policy(&mut transaction, KeySelector::ExampleKey, retry_policy)
+        .await?;
+    assert_eq!(transport.requests.lock().unwrap().len(), 3);
+    assert_ne!(transaction.signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_call_retry_policy_applies_to_batch_and_raw_signing() -> TurnkeyResult<()> {
+    let retry_policy = RetryPolicy {
+        max_retries: 1,
+        backoff: Duration::from_millis(1),
+        timeout: Some(Duration::from_secs(5)),
+        jitter: Jitter::None,
+    };
+
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::OK, &signed_activity_json()),
+    ]);
+    let turnkey_client = test_client(transport.clone())?;
+    let mut transactions = vec![unsigned_transaction()];
+    turnkey_client
+        .sign_transactions_with_retry_policy(
+            &mut transactions,
+            KeySelector::ExampleKey,
+            retry_policy,
+        )
+        .await?;
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::OK, &signed_activity_json()),
+    ]);
+    let turnkey_client = test_client(transport.clone())?;
+    turnkey_client
+        .sign_raw_components_with_retry_policy(
+            b"raw payload",
+            KeySelector::ExampleKey,
+            retry_policy,
+        )
+        .await?;
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_retry_is_restamped() -> TurnkeyResult<()> {
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::OK, &signed_activity_json()),
+    ]);
+    let turnkey_client = test_client(transport.clone())?.with_max_stamp_age(Duration::ZERO);
+    let retry_policy = RetryPolicy {
+        max_retries: 1,
+        backoff: Duration::from_millis(10),
+        timeout: None,
+        jitter: Jitter::None,
+    };
+
+    let mut transaction = unsigned_transaction();
+    turnkey_client
+        .sign_transaction_with_retry_policy(&mut transaction, KeySelector::ExampleKey, retry_policy)
+        .await?;
+
+    let requests = transport.requests.lock().unwrap();
+    let (_, first_headers, first_body) = &requests[0];
+    let (_, second_headers, second_body) = &requests[1];
+    let first_body: serde_json::Value = serde_json::from_str(first_body)?;
+    let second_body: serde_json::Value = serde_json::from_str(second_body)?;
+    assert_ne!(first_body["timestampMs"], second_body["timestampMs"]);
+    assert_eq!(first_body["parameters"], second_body["parameters"]);
+    assert_ne!(first_headers["X-Stamp"], second_headers["X-Stamp"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_retry_is_restamped_with_renamed_timestamp() -> TurnkeyResult<()> {
+    let transport = FakeTransport::sequence(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, "{}"),
+        (StatusCode::OK, &signed_activity_json()),
+    ]);
+    let renames = HashMap::from([("timestampMs".to_string(), "timestamp".to_string())]);
+    let turnkey_client = test_client(transport.clone())?
+        .with_api_version(ApiVersion::Renamed(renames))
+        .with_max_stamp_age(Duration::ZERO);
+    let retry_policy = RetryPolicy {
+        max_retries: 1,
+        backoff: Duration::from_millis(10),
+        timeout: None,
+        jitter: Jitter::None,
+    };
+
+    let mut transaction = unsigned_transaction();
+    turnkey_client
+        .sign_transaction_with_retry_policy(&mut transaction, KeySelector::ExampleKey, retry_policy)
+        .await?;
+
+    let requests = transport.requests.lock().unwrap();
+    let (_, first_headers, first_body) = &requests[0];
+    let (_, second_headers, second_body) = &requests[1];
+    let first_body: serde_json::Value = serde_json::from_str(first_body)?;
+    let second_body: serde_json::Value = serde_json::from_str(second_body)?;
+    assert!(second_body.get("timestampMs").is_none());
+    assert_ne!(first_body["timestamp"], second_body["timestamp"]);
+    assert_ne!(first_headers["X-Stamp"], second_headers["X-Stamp"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_activity_timestamps() -> TurnkeyResult<()> {
+    let response = signed_activity_json().replacen(
+        r#""activity":{"#,
+        r#""activity":{"createdAt":{"seconds":"1700000000","nanos":"500"},"updatedAt":{"seconds":1700000002,"nanos":0},"#,
+        1,
+    );
+    let transport = FakeTransport::new(StatusCode::OK, &response);
+    let turnkey_client = test_client(transport)?;
+
+    let activity = turnkey_client.get_activity("test-activity").await?.activity;
+    let created_at = activity.created_at.and_then(|t| t.to_datetime()).unwrap();
+    let updated_at = activity.updated_at.and_then(|t| t.to_datetime()).unwrap();
+    assert_eq!(created_at.timestamp(), 1_700_000_000);
+    assert_eq!(created_at.timestamp_subsec_nanos(), 500);
+    assert_eq!((updated_at - created_at).num_seconds(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_with_bytes() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let signed = turnkey_client
+        .sign_transaction_with_bytes(&mut transaction, KeySelector::ExampleKey)
+        .await?;
+
+    // the first signature slot follows the one-byte signature count
+    let signature_slot = 1..65;
+    assert_eq!(signed.unsigned_bytes.len(), signed.signed_bytes.len());
+    assert_eq!(&signed.unsigned_bytes[signature_slot.clone()], &[0u8; 64]);
+    assert_eq!(
+        &signed.signed_bytes[signature_slot.clone()],
+        signed.signature.as_ref()
+    );
+    assert_eq!(
+        signed.unsigned_bytes[signature_slot.end..],
+        signed.signed_bytes[signature_slot.end..]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_request_is_restamped() -> TurnkeyResult<()> {
+    let transport = SlowTransport::default();
+    let turnkey_client = test_client(transport.clone())?
+        .with_max_concurrent_requests(1)
+        .with_max_stamp_age(Duration::from_millis(10));
+
+    // the second request waits for the first one's permit for longer than the stamp age
+    let mut first = unsigned_transaction();
+    let mut second = unsigned_transaction();
+    let (first_result, second_result) = tokio::join!(
+        turnkey_client.sign_transaction(&mut first, KeySelector::ExampleKey),
+        turnkey_client.sign_transaction(&mut second, KeySelector::ExampleKey),
+    );
+    first_result?;
+    second_result?;
+
+    let timestamp_ages_ms = transport.timestamp_ages_ms.lock().unwrap();
+    assert_eq!(timestamp_ages_ms.len(), 2);
+    assert!(timestamp_ages_ms.iter().all(|age_ms| *age_ms < 50));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_wallet() -> TurnkeyResult<()> {
+    let response = activity_json(
+        "ACTIVITY_STATUS_COMPLETED",
+        r#"{"createWalletResult":{"walletId":"test-wallet","addresses":["11111111111111111111111111111111"]}}"#,
+    )
+    .replace("ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2", "ACTIVITY_TYPE_CREATE_WALLET");
+    let transport = FakeTransport::new(StatusCode::OK, &response);
+    let turnkey_client = test_client(transport.clone())?.with_activity_type_verification(true);
+
+    let result = turnkey_client
+        .create_wallet("test wallet", &[WalletAccountParams::solana(0)])
+        .await?;
+    assert_eq!(result.wallet_id, "test-wallet");
+    assert_eq!(result.addresses, vec!["11111111111111111111111111111111"]);
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[0];
+    assert!(url.ends_with("/public/v1/submit/create_wallet"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["type"], "ACTIVITY_TYPE_CREATE_WALLET");
+    assert_eq!(body["parameters"]["walletName"], "test wallet");
+    assert_eq!(
+        body["parameters"]["accounts"][0]["path"],
+        "m/44'/501'/0'/0'"
+    );
+    assert_eq!(
+        body["parameters"]["accounts"][0]["addressFormat"],
+        "ADDRESS_FORMAT_SOLANA"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_sub_organization() -> TurnkeyResult<()> {
+    let response = activity_json(
+        "ACTIVITY_STATUS_COMPLETED",
+        r#"{"createSubOrganizationResultV7":{"subOrganizationId":"test-sub-org","wallet":{"walletId":"test-wallet","addresses":["11111111111111111111111111111111"]},"rootUserIds":["test-user"]}}"#,
+    )
+    .replace(
+        "ACTIVITY_TYPE_SIGN_RAW_PAYLOAD_V2",
+        "ACTIVITY_TYPE_CREATE_SUB_ORGANIZATION_V7",
+    );
+    let transport = FakeTransport::new(StatusCode::OK, &response);
+    let turnkey_client = test_client(transport.clone())?.with_activity_type_verification(true);
+
+    let result = turnkey_client
+        .create_sub_organization(CreateSubOrgParams {
+            sub_organization_name: "test sub-org".to_string(),
+            root_users: vec![RootUserParams {
+                user_name: "root".to_string(),
+                user_email: None,
+                api_keys: vec![ApiKeyParams {
+                    api_key_name: "root key".to_string(),
+                    public_key: "02test".to_string(),
+                    curve_type: "API_KEY_CURVE_P256".to_string(),
+                    expiration_seconds: None,
+                }],
+                authenticators: vec![],
+                oauth_providers: vec![],
+            }],
+            root_quorum_threshold: 1,
+            wallet: Some(WalletParams {
+                wallet_name: "test wallet".to_string(),
+                accounts: vec![WalletAccountParams::solana(0)],
+                mnemonic_length: None,
+            }),
+        })
+        .await?;
+    assert_eq!(result.sub_organization_id, "test-sub-org");
+    assert_eq!(result.wallet.unwrap().wallet_id, "test-wallet");
+    assert_eq!(result.root_user_ids, vec!["test-user"]);
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[0];
+    assert!(url.ends_with("/public/v1/submit/create_sub_organization"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    let parameters = &body["parameters"];
+    assert_eq!(parameters["subOrganizationName"], "test sub-org");
+    assert_eq!(parameters["rootQuorumThreshold"], 1);
+    assert_eq!(
+        parameters["rootUsers"][0]["apiKeys"][0]["curveType"],
+        "API_KEY_CURVE_P256"
+    );
+    assert!(parameters["rootUsers"][0].get("userEmail").is_none());
+    assert_eq!(parameters["wallet"]["walletName"], "test wallet");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_whoami() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"organizationId":"test-org","organizationName":"Test Org","userId":"test-user","username":"tester"}"#,
+    );
+    let turnkey_client = test_client(transport.clone())?;
+
+    let whoami = turnkey_client.whoami().await?;
+    assert_eq!(whoami.organization_id, "test-org");
+    assert_eq!(whoami.organization_name, "Test Org");
+    assert_eq!(whoami.user_id, "test-user");
+    assert_eq!(whoami.username, "tester");
+    assert_eq!(whoami.api_key_id, None);
+
+    let requests = transport.requests.lock().unwrap();
+    assert!(requests[0].0.ends_with("/public/v1/query/whoami"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_check_ready() -> TurnkeyResult<()> {
+    let transport = FakeTransport::sequence(vec![
+        (
+            StatusCode::OK,
+            r#"{"organizationId":"test-org","organizationName":"Test Org","userId":"test-user","username":"tester"}"#,
+        ),
+        (
+            StatusCode::UNAUTHORIZED,
+            r#"{"code":16,"message":"unauthenticated","details":[]}"#,
+        ),
+    ]);
+    let turnkey_client = test_client(transport.clone())?;
+
+    turnkey_client.check_ready().await?;
+    let result = turnkey_client.check_ready().await;
+    assert!(matches!(result, Err(TurnkeyError::MethodError(_))));
+
+    let requests = transport.requests.lock().unwrap();
+    assert!(requests
+        .iter()
+        .all(|(url, _, _)| url.ends_with("/public/v1/query/whoami")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_wallet_accounts() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        r#"{"accounts":[{"walletAccountId":"test-account","organizationId":"test-org","walletId":"test-wallet","curve":"CURVE_ED25519","pathFormat":"PATH_FORMAT_BIP32","path":"m/44'/501'/0'/0'","addressFormat":"ADDRESS_FORMAT_SOLANA","address":"11111111111111111111111111111111"}]}"#,
+    );
+    let turnkey_client = test_client(transport.clone())?;
+
+    let accounts = turnkey_client.get_wallet_accounts("test-wallet").await?;
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].wallet_account_id, "test-account");
+    assert_eq!(accounts[0].path, "m/44'/501'/0'/0'");
+    assert_eq!(accounts[0].curve, "CURVE_ED25519");
+    assert_eq!(accounts[0].address, "11111111111111111111111111111111");
+
+    let requests = transport.requests.lock().unwrap();
+    assert!(requests[0]
+        .0
+        .ends_with("/public/v1/query/list_wallet_accounts"));
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+    assert_eq!(body["walletId"], "test-wallet");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_as_fee_payer_auto() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let signature = turnkey_client
+        .sign_as_fee_payer_auto(&mut transaction)
+        .await?;
+    assert_eq!(transaction.signatures[0], signature);
+
+    let mut unknown_payer = unsigned_transaction_for(&Pubkey::new_unique());
+    let result = turnkey_client
+        .sign_as_fee_payer_auto(&mut unknown_payer)
+        .await;
+    assert!(matches!(result, Err(TurnkeyError::OtherError(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_private_key() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &activity_json(
+            "ACTIVITY_STATUS_COMPLETED",
+            r#"{"exportPrivateKeyResult":{"privateKeyId":"test-private-key","exportBundle":"encrypted-bundle"}}"#,
+        ),
+    );
+    let turnkey_client = test_client(transport.clone())?;
+
+    let result = turnkey_client
+        .export_private_key("test-private-key", "04target")
+        .await?;
+    assert_eq!(result.private_key_id, "test-private-key");
+    assert_eq!(result.export_bundle, "encrypted-bundle");
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[0];
+    assert!(url.ends_with("/public/v1/submit/export_private_key"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["type"], "ACTIVITY_TYPE_EXPORT_PRIVATE_KEY");
+    assert_eq!(body["parameters"]["targetPublicKey"], "04target");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_private_key() -> TurnkeyResult<()> {
+    let whoami = r#"{"organizationId":"test-org","organizationName":"Test Org","userId":"test-user","username":"tester"}"#;
+    let imported = activity_json(
+        "ACTIVITY_STATUS_COMPLETED",
+        r#"{"importPrivateKeyResult":{"privateKeyId":"imported-key","addresses":[{"format":"ADDRESS_FORMAT_SOLANA","address":"11111111111111111111111111111111"}]}}"#,
+    );
+    let transport =
+        FakeTransport::sequence(vec![(StatusCode::OK, whoami), (StatusCode::OK, &imported)]);
+    let turnkey_client = test_client(transport.clone())?;
+
+    let result = turnkey_client
+        .import_private_key("encrypted-bundle", "imported key", Curve::Ed25519)
+        .await?;
+    assert_eq!(result.private_key_id, "imported-key");
+    assert_eq!(result.addresses[0].format, "ADDRESS_FORMAT_SOLANA");
+    assert_eq!(
+        result.addresses[0].address,
+        "11111111111111111111111111111111"
+    );
+
+    let requests = transport.requests.lock().unwrap();
+    let (url, _, body) = &requests[1];
+    assert!(url.ends_with("/public/v1/submit/import_private_key"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["type"], "ACTIVITY_TYPE_IMPORT_PRIVATE_KEY");
+    let parameters = &body["parameters"];
+    assert_eq!(parameters["userId"], "test-user");
+    assert_eq!(parameters["privateKeyName"], "imported key");
+    assert_eq!(parameters["encryptedBundle"], "encrypted-bundle");
+    assert_eq!(parameters["curve"], "CURVE_ED25519");
+    assert_eq!(parameters["addressFormats"][0], "ADDRESS_FORMAT_SOLANA");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_json_response_is_rejected() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, "<html>Bad Gateway</html>")
+        .with_content_type("text/html; charset=utf-8");
+    let turnkey_client = test_client(transport)?;
+
+    let result = turnkey_client.get_activity("test-activity").await;
+    assert!(matches!(
+        result,
+        Err(TurnkeyError::UnexpectedResponse { content_type, body, .. })
+            if content_type.as_deref() == Some("text/html; charset=utf-8")
+                && body == "<html>Bad Gateway</html>"
+    ));
+
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json())
+        .with_content_type("application/json; charset=utf-8");
+    test_client(transport)?
+        .get_activity("test-activity")
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_json_error_response_carries_status_and_request_id() -> TurnkeyResult<()> {
+    let mut transport = FakeTransport::new(StatusCode::BAD_GATEWAY, "<html>Bad Gateway</html>")
+        .with_content_type("text/html");
+    transport
+        .response_headers
+        .insert("x-request-id", HeaderValue::from_static("test-request-id"));
+    let turnkey_client = test_client(transport)?;
+
+    let error = turnkey_client
+        .get_activity("test-activity")
+        .await
+        .expect_err("expected an unexpected response error");
+    let message = error.to_string();
+    match error {
+        TurnkeyError::UnexpectedResponse {
+            status, request_id, ..
+        } => {
+            assert_eq!(status, Some(502));
+            assert_eq!(request_id.as_deref(), Some("test-request-id"));
+        }
+        other => panic!("expected an unexpected response error, got {:?}", other),
+    }
+    assert!(message.contains("HTTP status: 502"));
+    assert!(message.contains("request ID: test-request-id"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_malformed_error_body_carries_status() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::SERVICE_UNAVAILABLE, r#"{"unexpected":true}"#);
+    let turnkey_client = test_client(transport)?;
+
+    let result = turnkey_client.get_activity("test-activity").await;
+    assert!(matches!(
+        result,
+        Err(TurnkeyError::DeserializationError {
+            status: Some(503),
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_response_size() -> TurnkeyResult<()> {
+    let large_body = "x".repeat(1000);
+    let transport = FakeTransport::new(StatusCode::OK, &large_body);
+    let turnkey_client = test_client(transport)?.with_max_response_size(100);
+
+    let result = turnkey_client.get_activity("test-activity").await;
+    match result {
+        Err(TurnkeyError::UnexpectedResponse { body, .. }) => {
+            assert_eq!(body, format!("{}...", "x".repeat(256)));
+        }
+        other => panic!(
+            "expected an unexpected response error, got {:?}",
+            other.err()
+        ),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unexpected_response_body_is_deserialization_error() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, r#"{"unexpected":true}"#);
+    let turnkey_client = test_client(transport)?;
+
+    let result = turnkey_client.get_activity("test-activity").await;
+    assert!(matches!(
+        result,
+        Err(TurnkeyError::DeserializationError { body, .. }) if body == r#"{"unexpected":true}"#
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_offchain_message() -> TurnkeyResult<()> {
+    let keypair = Keypair::new();
+    let offchain_message = OffchainMessage::new(0, b"Sign in to example.com").unwrap();
+    let framed_message = offchain_message.serialize().unwrap();
+    assert!(framed_message.starts_with(b"\xffsolana offchain"));
+
+    let expected_hex = bytes_to_hex(keypair.sign_message(&framed_message).as_ref());
+    let transport = FakeTransport::new(
+        StatusCode::OK,
+        &signed_activity_json_with(&expected_hex[..64], &expected_hex[64..]),
+    );
+    let turnkey_client = ed25519_test_client(&keypair.pubkey(), transport.clone())?;
+
+    let signature = turnkey_client
+        .sign_offchain_message(b"Sign in to example.com", KeySelector::ExampleKey)
+        .await?;
+    assert!(offchain_message
+        .verify(&keypair.pubkey(), &signature)
+        .unwrap());
+
+    let requests = transport.requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].2).unwrap();
+    assert_eq!(body["parameters"]["payload"], bytes_to_hex(&framed_message));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transaction_encoded() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transaction = unsigned_transaction();
+    let base64 = turnkey_client
+        .sign_transaction_encoded(
+            &mut transaction,
+            KeySelector::ExampleKey,
+            TransactionEncoding::Base64,
+        )
+        .await?;
+    let base58 = turnkey_client
+        .sign_transaction_encoded(
+            &mut transaction,
+            KeySelector::ExampleKey,
+            TransactionEncoding::Base58,
+        )
+        .await?;
+
+    let serialized = bincode::serialize(&transaction).unwrap();
+    assert_eq!(
+        base64::engine::general_purpose::STANDARD
+            .decode(base64)
+            .unwrap(),
+        serialized
+    );
+    assert_eq!(
+        solana_sdk::bs58::decode(base58).into_vec().unwrap(),
+        serialized
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transactions_with_bounded_concurrency() -> TurnkeyResult<()> {
+    let transport = ConcurrencyTrackingTransport::default();
+    let turnkey_client = test_client(transport.clone())?.with_batch_concurrency(2);
+
+    let mut transactions = vec![unsigned_transaction(); 5];
+    let signatures = turnkey_client
+        .sign_transactions(&mut transactions, KeySelector::ExampleKey)
+        .await?;
+
+    assert_eq!(signatures.len(), 5);
+    for (transaction, signature) in transactions.iter().zip(&signatures) {
+        assert_eq!(transaction.signatures[0], *signature);
+    }
+    assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_transactions_collect_keeps_going_after_failure() -> TurnkeyResult<()> {
+    let transport = FakeTransport::new(StatusCode::OK, &signed_activity_json());
+    let turnkey_client = test_client(transport)?;
+
+    let mut transactions = vec![
+        unsigned_transaction(),
+        unsigned_transaction_for(&Pubkey::new_unique()),
+        unsigned_transaction(),
+    ];
+    let results = turnkey_client
+        .sign_transactions_collect(&mut transactions, KeySelector::ExampleKey)
+        .await;
+
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(TurnkeyError::OtherError(_))));
+    assert!(results[2].is_ok());
+    assert_ne!(transactions[2].signatures[0], Signature::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_activity_approvers() -> TurnkeyResult<()> {
+    let response = activity_json("ACTIVITY_STATUS_CONSENSUS_NEEDED", "null").replacen(
+        r#""activity":{"#,
+        r#""activity":{"votes":[{"id":"vote-1","userId":"approver-1","activityId":"test-activity","selection":"VOTE_SELECTION_APPROVED","createdAt":{"seconds":"1700000000","nanos":"0"}},{"id":"vote-2","userId":"approver-2","activityId":"test-activity","selection":"VOTE_SELECTION_REJECTED"}],"#,
+        1,
+    );
+    let transport = FakeTransport::new(StatusCode::OK, &response);
+    let turnkey_client = test_client(transport)?;
+
+    let activity = turnkey_client.get_activity("test-activity").await?.activity;
+    let approvals = activity.approvals().collect::<Vec<_>>();
+    assert_eq!(activity.votes.len(), 2);
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals[0].user_id, "approver-1");
+    assert_eq!(
+        approvals[0]
+            .created_at
+            .and_then(|t| t.to_datetime())
+            .map(|t| t.timestamp()),
+        Some(1_700_000_000)
+    );
+
+    Ok(())
+}